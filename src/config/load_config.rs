@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::Path;
+
+use super::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+
+/// 入力ディレクトリ直下で自動的に探す設定ファイル名
+pub const CONFIG_FILE_NAME: &str = "ubi-doc.toml";
+
+/// 設定ファイルを読み込む
+/// - config_path が指定されていればそのファイルを読み込む (存在しなければエラー)
+/// - 指定がなければ input_root 直下の ubi-doc.toml を探し、なければデフォルト設定を返す
+pub fn load_config(
+    config_path: Option<&Path>,
+    input_root: &Path,
+) -> Result<UbiDocConfig, UbiDocError> {
+    let path = match config_path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let discovered = input_root.join(CONFIG_FILE_NAME);
+            if !discovered.is_file() {
+                return Ok(UbiDocConfig::default());
+            }
+            discovered
+        }
+    };
+
+    let content = fs::read_to_string(&path).map_err(|e| {
+        UbiDocError::Config(format!("Failed to read config file {:?}: {}", path, e))
+    })?;
+    let config: UbiDocConfig = toml::from_str(&content).map_err(|e| {
+        UbiDocError::Config(format!("Failed to parse config file {:?}: {}", path, e))
+    })?;
+
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    Ok(config.resolve_paths(base_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_config_discovered() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let mut file = File::create(dir.path().join(CONFIG_FILE_NAME))?;
+        writeln!(file, "input = [\"src\"]\noutput = \"out\"")?;
+
+        let config = load_config(None, dir.path())?;
+
+        let expected_input = dir.path().join("src").to_string_lossy().to_string();
+        assert_eq!(config.input, vec![expected_input]);
+        assert_eq!(
+            config.output,
+            Some(dir.path().join("out").to_string_lossy().to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_config_not_found() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+
+        // 自動探索で見つからなければデフォルト設定
+        let config = load_config(None, dir.path())?;
+        assert_eq!(config, UbiDocConfig::default());
+
+        // 明示的に指定したファイルが存在しなければエラー
+        let missing = dir.path().join("missing.toml");
+        assert!(load_config(Some(&missing), dir.path()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_config_invalid() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let path = dir.path().join("custom.toml");
+        let mut file = File::create(&path)?;
+        writeln!(file, "formats = [\"pdf\"]")?;
+
+        assert!(load_config(Some(&path), dir.path()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,553 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::diagnostics::diagnostic::Severity;
+
+pub const DEFAULT_INPUT: &str = "sample";
+pub const DEFAULT_OUTPUT: &str = "docs";
+pub const DEFAULT_REPO: &str = "Glider2355/ubi-doc";
+pub const DEFAULT_BRANCH: &str = "main";
+
+/// ubi-doc.toml の内容
+/// 値が指定されていない項目は None / 空で保持し、CLI 引数で上書きできるようにする
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct UbiDocConfig {
+    /// 入力ディレクトリのパス
+    pub input: Vec<String>,
+    /// 出力ディレクトリのパス
+    pub output: Option<String>,
+    /// 走査対象に含めるファイルの glob
+    pub include: Vec<String>,
+    /// 走査対象から除外するファイルの glob
+    pub exclude: Vec<String>,
+    /// .gitignore / .ignore に書かれたファイルを除外するかどうか (デフォルト: true)
+    pub respect_ignore_files: Option<bool>,
+    /// シンボリックリンクをたどるかどうか (デフォルト: false)
+    pub follow_symlinks: Option<bool>,
+    /// UTF-8 でデコードできなかった場合に試すエンコーディング (例: Shift_JIS, EUC-JP)
+    pub encodings: Vec<String>,
+    /// ディレクトリごとのデフォルトのコンテキスト
+    pub contexts: Vec<ContextMapping>,
+    /// パッケージ・名前空間からコンテキストを決めるルール
+    pub namespace_contexts: Vec<NamespaceContextRule>,
+    /// 解析対象の言語 (空の場合はすべての対応言語)
+    pub languages: Vec<String>,
+    /// Docコメントのタグ名
+    pub tags: TagConfig,
+    /// 出力形式 (空の場合は html)
+    pub formats: Vec<OutputFormat>,
+    /// ソースコードへのリンク
+    pub link: LinkConfig,
+    /// Lint ルールごとの重要度
+    pub lint: BTreeMap<String, LintRuleLevel>,
+    /// HTML テンプレートのディレクトリ
+    pub template_dir: Option<String>,
+    /// @owner がない用語の担当チームを決める CODEOWNERS ファイル
+    /// (未指定の場合は入力ディレクトリ・カレントディレクトリから探す)
+    pub codeowners: Option<String>,
+    /// HTML の画面の言語 (組み込みは en / ja。デフォルト: en)
+    pub ui_lang: Option<String>,
+    /// HTML の画面の文言のカタログファイル (組み込みのカタログより優先する)
+    pub ui_catalog: Option<String>,
+    /// HTML に用語の関連図を埋め込むときに読み込む Mermaid のスクリプト (URL またはファイル)
+    /// ファイルは出力ディレクトリにコピーする。未指定の場合は関連図を埋め込まない
+    pub mermaid_script: Option<String>,
+}
+
+/// Docコメントから読み取るタグ名 (先頭の @ は含まない)
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+#[serde(default)]
+pub struct TagConfig {
+    pub ubiquitous: String,
+    pub context: String,
+    pub description: String,
+    /// 用語の別名
+    pub alias: String,
+    /// 用語の同義語 (alias と同じく別名として扱う)
+    pub synonym: String,
+    /// 使用をやめた用語 (値は置き換え先の用語)
+    /// Javadoc などの標準の @deprecated はコードの非推奨を表すため、既定では別のタグ名にする
+    pub deprecated: String,
+    /// 関連する用語
+    pub see: String,
+    /// 関連する用語 (see と同じく扱う)
+    pub related: String,
+    /// DDD の構成要素としての種類 (値は aggregate / entity / value object など)
+    pub stereotype: String,
+    /// 集約ルート
+    pub aggregate: String,
+    /// エンティティ (値は所属する集約ルートの用語。以下同じ)
+    pub entity: String,
+    /// 値オブジェクト
+    pub value_object: String,
+    /// ドメインイベント
+    pub event: String,
+    /// ドメインサービス
+    pub service: String,
+    /// ポリシー
+    pub policy: String,
+    /// Ruby の module を enum として扱う印 (module 直下の定数を列挙子にする)
+    #[serde(rename = "enum")]
+    pub enumeration: String,
+    /// 用語に関する不変条件 (複数指定可)
+    pub invariant: String,
+    /// 用語に関する業務ルール (invariant と同じく扱う)
+    pub rule: String,
+    /// 用語の使用例 (複数指定可)
+    /// PHPDoc の標準の @example はサンプルファイルを指すため、既定では別のタグ名にする
+    pub example: String,
+    /// 用語を担当するチーム (カンマ区切り)
+    pub owner: String,
+    /// タグの別名と、対応するタグ名 (例: 用語 = "ubiquitous"、bc = "context")
+    pub aliases: BTreeMap<String, String>,
+    /// 用語ごとに値を集めて、出力に列として追加するタグ (例: jira, status)
+    pub extra: Vec<String>,
+}
+
+impl Default for TagConfig {
+    fn default() -> Self {
+        TagConfig {
+            ubiquitous: "ubiquitous".to_string(),
+            context: "context".to_string(),
+            description: "description".to_string(),
+            alias: "alias".to_string(),
+            synonym: "synonym".to_string(),
+            deprecated: "deprecatedTerm".to_string(),
+            see: "see".to_string(),
+            related: "related".to_string(),
+            stereotype: "stereotype".to_string(),
+            aggregate: "aggregate".to_string(),
+            entity: "entity".to_string(),
+            value_object: "valueObject".to_string(),
+            event: "event".to_string(),
+            service: "service".to_string(),
+            policy: "policy".to_string(),
+            enumeration: "enum".to_string(),
+            invariant: "invariant".to_string(),
+            rule: "rule".to_string(),
+            example: "termExample".to_string(),
+            owner: "owner".to_string(),
+            aliases: BTreeMap::new(),
+            extra: Vec::new(),
+        }
+    }
+}
+
+/// パスではなく URL (http://, https://, //) かどうか
+pub fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://") || path.starts_with("//")
+}
+
+/// パスの glob と、@context が書かれていない用語に使うコンテキストの対応
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+pub struct ContextMapping {
+    /// 設定ファイルのディレクトリからの相対パス (例: services/ordering/**)
+    pub path: String,
+    pub context: String,
+}
+
+/// パッケージ・名前空間・モジュールの正規表現と、@context が書かれていない用語に使うコンテキストの対応
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+pub struct NamespaceContextRule {
+    /// 名前空間に一致させる正規表現 (例: ^com\.acme\.(\w+))
+    pub pattern: String,
+    /// コンテキスト名。$1 や ${name} でキャプチャを参照できる (デフォルト: $1)
+    #[serde(default = "default_namespace_context")]
+    pub context: String,
+}
+
+fn default_namespace_context() -> String {
+    "$1".to_string()
+}
+
+/// 出力形式
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Html,
+    Json,
+    Markdown,
+    /// 用語の関連を Graphviz の DOT 形式で出力する
+    Dot,
+    /// 用語の関連を Mermaid 形式で出力する
+    Mermaid,
+    /// クラスが使っている型から求めたコンテキストマップを JSON・DOT・Mermaid で出力する
+    #[serde(rename = "context-map")]
+    ContextMap,
+}
+
+/// ソースコードへのリンクを生成するサービス
+#[derive(Debug, Default, Deserialize, PartialEq, Clone, Copy, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkProvider {
+    #[default]
+    #[value(name = "github")]
+    GitHub,
+    #[value(name = "gitlab")]
+    GitLab,
+    None,
+}
+
+/// ソースコードへのリンク設定
+#[derive(Debug, Default, Deserialize, PartialEq, Clone)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LinkConfig {
+    pub provider: Option<LinkProvider>,
+    /// リポジトリ (例: owner/repo)
+    pub repo: Option<String>,
+    /// ブランチ名
+    pub branch: Option<String>,
+    /// GitHub Enterprise / セルフホスト GitLab などの URL (例: https://gitlab.example.com)
+    pub base_url: Option<String>,
+}
+
+impl LinkConfig {
+    /// ファイルパスと行番号からソースコードへの URL を生成する
+    pub fn url(&self, file_path: &str, line_number: usize) -> String {
+        let repo = self.repo.as_deref().unwrap_or(DEFAULT_REPO);
+        let branch = self.branch.as_deref().unwrap_or(DEFAULT_BRANCH);
+
+        match self.provider.unwrap_or_default() {
+            LinkProvider::GitHub => format!(
+                "{}/{}/blob/{}/{}#L{}",
+                self.base_url("https://github.com"),
+                repo,
+                branch,
+                file_path,
+                line_number
+            ),
+            LinkProvider::GitLab => format!(
+                "{}/{}/-/blob/{}/{}#L{}",
+                self.base_url("https://gitlab.com"),
+                repo,
+                branch,
+                file_path,
+                line_number
+            ),
+            LinkProvider::None => String::new(),
+        }
+    }
+
+    fn base_url<'a>(&'a self, default: &'a str) -> &'a str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+    }
+}
+
+/// Lint ルールの重要度設定
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LintRuleLevel {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LintRuleLevel {
+    /// off の場合は None
+    pub fn to_severity(self) -> Option<Severity> {
+        match self {
+            LintRuleLevel::Off => None,
+            LintRuleLevel::Info => Some(Severity::Info),
+            LintRuleLevel::Warning => Some(Severity::Warning),
+            LintRuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+impl UbiDocConfig {
+    /// other で指定されている項目で上書きする (CLI 引数 > 設定ファイル)
+    pub fn override_with(mut self, other: UbiDocConfig) -> Self {
+        if !other.input.is_empty() {
+            self.input = other.input;
+        }
+        if other.output.is_some() {
+            self.output = other.output;
+        }
+        if !other.include.is_empty() {
+            self.include = other.include;
+        }
+        if !other.exclude.is_empty() {
+            self.exclude = other.exclude;
+        }
+        if other.respect_ignore_files.is_some() {
+            self.respect_ignore_files = other.respect_ignore_files;
+        }
+        if other.follow_symlinks.is_some() {
+            self.follow_symlinks = other.follow_symlinks;
+        }
+        if !other.encodings.is_empty() {
+            self.encodings = other.encodings;
+        }
+        if !other.contexts.is_empty() {
+            self.contexts = other.contexts;
+        }
+        if !other.namespace_contexts.is_empty() {
+            self.namespace_contexts = other.namespace_contexts;
+        }
+        if !other.languages.is_empty() {
+            self.languages = other.languages;
+        }
+        if !other.formats.is_empty() {
+            self.formats = other.formats;
+        }
+        if other.link.provider.is_some() {
+            self.link.provider = other.link.provider;
+        }
+        if other.link.repo.is_some() {
+            self.link.repo = other.link.repo;
+        }
+        if other.link.branch.is_some() {
+            self.link.branch = other.link.branch;
+        }
+        if other.link.base_url.is_some() {
+            self.link.base_url = other.link.base_url;
+        }
+        self.lint.extend(other.lint);
+        if other.template_dir.is_some() {
+            self.template_dir = other.template_dir;
+        }
+        if other.codeowners.is_some() {
+            self.codeowners = other.codeowners;
+        }
+        if other.ui_lang.is_some() {
+            self.ui_lang = other.ui_lang;
+        }
+        if other.ui_catalog.is_some() {
+            self.ui_catalog = other.ui_catalog;
+        }
+        if other.mermaid_script.is_some() {
+            self.mermaid_script = other.mermaid_script;
+        }
+        self
+    }
+
+    /// 設定ファイルに書かれた相対パスを、設定ファイルのディレクトリ基準に解決する
+    pub fn resolve_paths(mut self, base_dir: &Path) -> Self {
+        let resolve = |path: &String| base_dir.join(path).to_string_lossy().to_string();
+        self.input = self.input.iter().map(resolve).collect();
+        self.output = self.output.as_ref().map(resolve);
+        self.template_dir = self.template_dir.as_ref().map(resolve);
+        self.ui_catalog = self.ui_catalog.as_ref().map(resolve);
+        self.codeowners = self.codeowners.as_ref().map(resolve);
+        self.mermaid_script = self.mermaid_script.as_ref().map(|script| {
+            if is_url(script) {
+                script.clone()
+            } else {
+                resolve(script)
+            }
+        });
+        for mapping in self.contexts.iter_mut() {
+            mapping.path = resolve(&mapping.path);
+        }
+        self
+    }
+
+    pub fn input_paths(&self) -> Vec<String> {
+        if self.input.is_empty() {
+            vec![DEFAULT_INPUT.to_string()]
+        } else {
+            self.input.clone()
+        }
+    }
+
+    pub fn output_path(&self) -> String {
+        self.output
+            .clone()
+            .unwrap_or_else(|| DEFAULT_OUTPUT.to_string())
+    }
+
+    pub fn output_formats(&self) -> Vec<OutputFormat> {
+        if self.formats.is_empty() {
+            vec![OutputFormat::Html]
+        } else {
+            self.formats.clone()
+        }
+    }
+
+    /// 言語名 (php, kotlin, ruby, java) が解析対象かどうか
+    pub fn is_language_enabled(&self, language: &str) -> bool {
+        self.languages.is_empty()
+            || self
+                .languages
+                .iter()
+                .any(|enabled| enabled.eq_ignore_ascii_case(language))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: UbiDocConfig = toml::from_str(
+            r#"
+input = ["src"]
+output = "public/ubi-doc"
+exclude = ["**/vendor/**"]
+respect-ignore-files = false
+languages = ["php", "java"]
+formats = ["html", "json"]
+template-dir = "templates"
+ui-lang = "ja"
+
+[tags]
+ubiquitous = "term"
+extra = ["jira", "status"]
+
+[tags.aliases]
+bc = "context"
+
+[link]
+provider = "gitlab"
+repo = "group/project"
+
+[lint]
+term-variant = "off"
+
+[[contexts]]
+path = "services/ordering/**"
+context = "Ordering"
+
+[[namespace-contexts]]
+pattern = '^com\.acme\.(\w+)'
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.input, vec!["src"]);
+        assert_eq!(config.output_path(), "public/ubi-doc");
+        assert_eq!(config.exclude, vec!["**/vendor/**"]);
+        assert_eq!(config.respect_ignore_files, Some(false));
+        assert_eq!(config.follow_symlinks, None);
+        assert!(config.is_language_enabled("PHP"));
+        assert!(!config.is_language_enabled("ruby"));
+        assert_eq!(
+            config.output_formats(),
+            vec![OutputFormat::Html, OutputFormat::Json]
+        );
+        assert_eq!(config.tags.ubiquitous, "term");
+        assert_eq!(config.tags.context, "context");
+        assert_eq!(config.tags.aliases["bc"], "context");
+        assert_eq!(config.tags.extra, vec!["jira", "status"]);
+        assert_eq!(config.link.provider, Some(LinkProvider::GitLab));
+        assert_eq!(config.lint["term-variant"], LintRuleLevel::Off);
+        assert_eq!(config.template_dir, Some("templates".to_string()));
+        assert_eq!(config.ui_lang, Some("ja".to_string()));
+        assert_eq!(
+            config.contexts,
+            vec![ContextMapping {
+                path: "services/ordering/**".to_string(),
+                context: "Ordering".to_string(),
+            }]
+        );
+        assert_eq!(
+            config.namespace_contexts,
+            vec![NamespaceContextRule {
+                pattern: r"^com\.acme\.(\w+)".to_string(),
+                context: "$1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = UbiDocConfig::default();
+        assert_eq!(config.input_paths(), vec![DEFAULT_INPUT]);
+        assert_eq!(config.output_path(), DEFAULT_OUTPUT);
+        assert_eq!(config.output_formats(), vec![OutputFormat::Html]);
+        assert!(config.is_language_enabled("kotlin"));
+    }
+
+    #[test]
+    fn test_override_with() {
+        let file_config = UbiDocConfig {
+            input: vec!["src".to_string()],
+            output: Some("docs".to_string()),
+            languages: vec!["php".to_string()],
+            ..UbiDocConfig::default()
+        };
+        let cli_config = UbiDocConfig {
+            output: Some("out".to_string()),
+            ..UbiDocConfig::default()
+        };
+
+        let config = file_config.override_with(cli_config);
+
+        assert_eq!(config.input, vec!["src"]);
+        assert_eq!(config.output, Some("out".to_string()));
+        assert_eq!(config.languages, vec!["php"]);
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let config = UbiDocConfig {
+            input: vec!["src".to_string()],
+            template_dir: Some("templates".to_string()),
+            contexts: vec![ContextMapping {
+                path: "services/billing".to_string(),
+                context: "Billing".to_string(),
+            }],
+            ..UbiDocConfig::default()
+        }
+        .resolve_paths(Path::new("project"));
+
+        assert_eq!(config.input, vec!["project/src"]);
+        assert_eq!(config.contexts[0].path, "project/services/billing");
+        assert_eq!(config.output, None);
+        assert_eq!(config.template_dir, Some("project/templates".to_string()));
+
+        // URL はそのまま使う
+        let resolve_script = |script: &str| {
+            UbiDocConfig {
+                mermaid_script: Some(script.to_string()),
+                ..UbiDocConfig::default()
+            }
+            .resolve_paths(Path::new("project"))
+            .mermaid_script
+        };
+        assert_eq!(
+            resolve_script("vendor/mermaid.mjs"),
+            Some("project/vendor/mermaid.mjs".to_string())
+        );
+        assert_eq!(
+            resolve_script("https://example.com/mermaid.mjs"),
+            Some("https://example.com/mermaid.mjs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_link_url() {
+        let github = LinkConfig {
+            repo: Some("owner/repo".to_string()),
+            branch: Some("dev".to_string()),
+            ..LinkConfig::default()
+        };
+        assert_eq!(
+            github.url("src/Order.php", 3),
+            "https://github.com/owner/repo/blob/dev/src/Order.php#L3"
+        );
+
+        let gitlab = LinkConfig {
+            provider: Some(LinkProvider::GitLab),
+            base_url: Some("https://gitlab.example.com/".to_string()),
+            ..github.clone()
+        };
+        assert_eq!(
+            gitlab.url("src/Order.php", 3),
+            "https://gitlab.example.com/owner/repo/-/blob/dev/src/Order.php#L3"
+        );
+
+        let none = LinkConfig {
+            provider: Some(LinkProvider::None),
+            ..github
+        };
+        assert_eq!(none.url("src/Order.php", 3), "");
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::config::ubi_doc_config::TagConfig;
+use crate::parser::class_doc::DeclarationKind;
+use crate::parser::get_class_declaration_list::SourceClassDeclaration;
+
+/// 用語集に登録済みのクラス数と全クラス数
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CoverageCount {
+    pub documented: usize,
+    pub total: usize,
+}
+
+impl CoverageCount {
+    fn add(&mut self, documented: bool) {
+        self.total += 1;
+        if documented {
+            self.documented += 1;
+        }
+    }
+
+    /// カバレッジ(%)。クラスが1つもない場合は 100% とみなす
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// @ubiquitous が付いていないクラス
+#[derive(Debug, PartialEq)]
+pub struct UndocumentedClass {
+    pub class_name: String,
+    pub file_path: String,
+    pub line_number: usize,
+}
+
+/// ドメインクラスの用語集カバレッジ
+#[derive(Debug)]
+pub struct CoverageReport {
+    pub total: CoverageCount,
+    pub by_language: BTreeMap<String, CoverageCount>,
+    pub by_directory: BTreeMap<String, CoverageCount>,
+    pub undocumented: Vec<UndocumentedClass>,
+}
+
+impl CoverageReport {
+    /// kinds: 数える宣言の種類 (空の場合はメンバー以外のすべて)
+    pub fn new(
+        declarations: &[SourceClassDeclaration],
+        tags: &TagConfig,
+        kinds: &[DeclarationKind],
+    ) -> Self {
+        let mut report = CoverageReport {
+            total: CoverageCount::default(),
+            by_language: BTreeMap::new(),
+            by_directory: BTreeMap::new(),
+            undocumented: Vec::new(),
+        };
+
+        // メソッド・プロパティ・定数・列挙子と、名前空間としてだけ使われている module は
+        // クラスのカバレッジに含めない
+        for source in declarations.iter().filter(|source| {
+            let kind = source.declaration.kind;
+            !kind.is_member()
+                && (kinds.is_empty() || kinds.contains(&kind))
+                && !is_namespace_module(source, declarations)
+        }) {
+            let documented = source
+                .declaration
+                .has_ubiquitous(tags, source.comment_style);
+            let directory = Path::new(&source.file_path)
+                .parent()
+                .map(|parent| parent.to_string_lossy().replace("\\", "/"))
+                .unwrap_or_default();
+
+            report.total.add(documented);
+            report
+                .by_language
+                .entry(source.language.to_string())
+                .or_default()
+                .add(documented);
+            report
+                .by_directory
+                .entry(directory)
+                .or_default()
+                .add(documented);
+
+            if !documented {
+                report.undocumented.push(UndocumentedClass {
+                    class_name: source.declaration.class_name.clone(),
+                    file_path: source.file_path.clone(),
+                    line_number: source.declaration.line_number,
+                });
+            }
+        }
+
+        report
+            .undocumented
+            .sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+        report
+    }
+
+    /// 全体のカバレッジが閾値(%)以上かどうか
+    pub fn meets(&self, min_coverage: f64) -> bool {
+        self.total.percentage() >= min_coverage
+    }
+
+    /// テキスト形式のレポートを生成する
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        writeln!(output, "Coverage: {}", format_count(&self.total)).unwrap();
+
+        writeln!(output, "\nBy language:").unwrap();
+        for (language, count) in &self.by_language {
+            writeln!(output, "  {:<10} {}", language, format_count(count)).unwrap();
+        }
+
+        writeln!(output, "\nBy directory:").unwrap();
+        let width = self.by_directory.keys().map(|d| d.len()).max().unwrap_or(0);
+        for (directory, count) in &self.by_directory {
+            writeln!(
+                output,
+                "  {:<width$} {}",
+                directory,
+                format_count(count),
+                width = width
+            )
+            .unwrap();
+        }
+
+        if !self.undocumented.is_empty() {
+            writeln!(output, "\nClasses without @ubiquitous:").unwrap();
+            for class in &self.undocumented {
+                writeln!(
+                    output,
+                    "  {}:{} {}",
+                    class.file_path, class.line_number, class.class_name
+                )
+                .unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+/// 型の宣言だけを含み、メソッドや定数を持たない module (Ruby の名前空間) かどうか
+fn is_namespace_module(
+    source: &SourceClassDeclaration,
+    declarations: &[SourceClassDeclaration],
+) -> bool {
+    if source.declaration.kind != DeclarationKind::Module {
+        return false;
+    }
+    let name = Some(&source.declaration.qualified_name);
+    let mut in_same_file = declarations
+        .iter()
+        .filter(|other| other.file_path == source.file_path);
+    let has_members = in_same_file.clone().any(|other| {
+        other.declaration.kind.is_member() && other.declaration.parent.as_ref() == name
+    });
+    let has_types = in_same_file.any(|other| {
+        !other.declaration.kind.is_member() && other.declaration.namespace.as_ref() == name
+    });
+    has_types && !has_members
+}
+
+fn format_count(count: &CoverageCount) -> String {
+    format!(
+        "{}/{} ({:.1}%)",
+        count.documented,
+        count.total,
+        count.percentage()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::class_doc::{ClassDeclaration, DeclarationKind};
+    use crate::parser::doc_comment::CommentStyle;
+
+    fn declaration(
+        class_name: &str,
+        file_path: &str,
+        language: &'static str,
+        doc_comment: Option<&str>,
+    ) -> SourceClassDeclaration {
+        SourceClassDeclaration {
+            declaration: ClassDeclaration {
+                class_name: class_name.to_string(),
+                kind: DeclarationKind::Class,
+                qualified_name: class_name.to_string(),
+                parent: None,
+                line_number: 5,
+                doc_comment: doc_comment.map(|doc| (doc.to_string(), 1)),
+                namespace: None,
+                type_references: Vec::new(),
+            },
+            file_path: file_path.to_string(),
+            language,
+            comment_style: CommentStyle::Block,
+        }
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        let declarations = vec![
+            declaration(
+                "Order",
+                "src/order/Order.php",
+                "PHP",
+                Some("/** @ubiquitous Order */"),
+            ),
+            declaration(
+                "OrderLine",
+                "src/order/OrderLine.php",
+                "PHP",
+                Some("/** just doc */"),
+            ),
+            declaration("Invoice", "src/billing/Invoice.java", "Java", None),
+            declaration(
+                "Bill",
+                "src/billing/Bill.java",
+                "Java",
+                Some("/** @ubiquitous Bill */"),
+            ),
+        ];
+
+        let report = CoverageReport::new(&declarations, &TagConfig::default(), &[]);
+
+        assert_eq!(
+            report.total,
+            CoverageCount {
+                documented: 2,
+                total: 4
+            }
+        );
+        assert_eq!(report.total.percentage(), 50.0);
+        assert_eq!(
+            report.by_language["PHP"],
+            CoverageCount {
+                documented: 1,
+                total: 2
+            }
+        );
+        assert_eq!(
+            report.by_directory["src/billing"],
+            CoverageCount {
+                documented: 1,
+                total: 2
+            }
+        );
+        assert_eq!(report.undocumented.len(), 2);
+        assert_eq!(report.undocumented[0].class_name, "Invoice");
+        assert_eq!(report.undocumented[1].class_name, "OrderLine");
+
+        assert!(report.meets(50.0));
+        assert!(!report.meets(50.1));
+    }
+
+    #[test]
+    fn test_coverage_report_render() {
+        let declarations = vec![declaration(
+            "Invoice",
+            "src/billing/Invoice.java",
+            "Java",
+            None,
+        )];
+
+        let output = CoverageReport::new(&declarations, &TagConfig::default(), &[]).render();
+
+        assert!(output.contains("Coverage: 0/1 (0.0%)"));
+        assert!(output.contains("Java       0/1 (0.0%)"));
+        assert!(output.contains("src/billing/Invoice.java:5 Invoice"));
+    }
+
+    #[test]
+    fn test_coverage_report_ignores_operations() {
+        let mut operation = declaration("place", "src/order/Order.php", "PHP", None);
+        operation.declaration.kind = DeclarationKind::Operation;
+        let declarations = vec![
+            declaration(
+                "Order",
+                "src/order/Order.php",
+                "PHP",
+                Some("/** @ubiquitous Order */"),
+            ),
+            operation,
+        ];
+
+        let report = CoverageReport::new(&declarations, &TagConfig::default(), &[]);
+
+        assert_eq!(
+            report.total,
+            CoverageCount {
+                documented: 1,
+                total: 1
+            }
+        );
+        assert!(report.undocumented.is_empty());
+    }
+
+    #[test]
+    fn test_coverage_report_ignores_namespace_modules() {
+        let mut namespace = declaration("Billing", "lib/billing.rb", "Ruby", None);
+        namespace.declaration.kind = DeclarationKind::Module;
+        let mut invoice = declaration("Invoice", "lib/billing.rb", "Ruby", None);
+        invoice.declaration.qualified_name = "Billing::Invoice".to_string();
+        invoice.declaration.namespace = Some("Billing".to_string());
+        let mut helpers = declaration("Helpers", "lib/helpers.rb", "Ruby", None);
+        helpers.declaration.kind = DeclarationKind::Module;
+        let mut format = declaration("format", "lib/helpers.rb", "Ruby", None);
+        format.declaration.kind = DeclarationKind::Operation;
+        format.declaration.parent = Some("Helpers".to_string());
+        let mut status = declaration("Status", "lib/status.rb", "Ruby", None);
+        status.declaration.kind = DeclarationKind::Module;
+
+        let report = CoverageReport::new(
+            &[namespace, invoice, helpers, format, status],
+            &TagConfig::default(),
+            &[],
+        );
+
+        // 型だけを含む Billing は数えず、メソッドを持つ Helpers や中身のない Status は数える
+        let undocumented: Vec<_> = report
+            .undocumented
+            .iter()
+            .map(|class| class.class_name.as_str())
+            .collect();
+        assert_eq!(undocumented, vec!["Invoice", "Helpers", "Status"]);
+    }
+
+    #[test]
+    fn test_coverage_report_kinds() {
+        let mut repository =
+            declaration("OrderRepository", "src/OrderRepository.kt", "Kotlin", None);
+        repository.declaration.kind = DeclarationKind::Interface;
+        let declarations = vec![
+            declaration("Order", "src/Order.kt", "Kotlin", None),
+            repository,
+        ];
+
+        let report = CoverageReport::new(
+            &declarations,
+            &TagConfig::default(),
+            &[DeclarationKind::Class],
+        );
+
+        assert_eq!(report.total.total, 1);
+        assert_eq!(report.undocumented[0].class_name, "Order");
+    }
+
+    #[test]
+    fn test_coverage_report_custom_tag() {
+        let declarations = vec![
+            declaration("Order", "src/Order.php", "PHP", Some("/** @term Order */")),
+            declaration(
+                "OrderLine",
+                "src/OrderLine.php",
+                "PHP",
+                Some("/** @ubiquitous OrderLine */"),
+            ),
+            declaration(
+                "Invoice",
+                "src/Invoice.php",
+                "PHP",
+                Some("/** @termx Invoice */"),
+            ),
+        ];
+        let tags = TagConfig {
+            ubiquitous: "term".to_string(),
+            ..TagConfig::default()
+        };
+
+        let report = CoverageReport::new(&declarations, &tags, &[]);
+
+        // 設定したタグ名と完全に一致するタグだけを数える
+        assert_eq!(
+            report.total,
+            CoverageCount {
+                documented: 1,
+                total: 3
+            }
+        );
+        assert_eq!(report.undocumented[0].class_name, "Invoice");
+        assert_eq!(report.undocumented[1].class_name, "OrderLine");
+    }
+
+    #[test]
+    fn test_coverage_report_alias_tag() {
+        let declarations = vec![
+            declaration("Order", "src/Order.php", "PHP", Some("/** @用語 注文 */")),
+            declaration(
+                "Invoice",
+                "src/Invoice.php",
+                "PHP",
+                Some("/** @ubiquitous Invoice */"),
+            ),
+            declaration("Money", "src/Money.php", "PHP", Some("/** @値 金額 */")),
+        ];
+        let tags = TagConfig {
+            aliases: BTreeMap::from([("用語".to_string(), "ubiquitous".to_string())]),
+            ..TagConfig::default()
+        };
+
+        let report = CoverageReport::new(&declarations, &tags, &[]);
+
+        // 別名のタグも用語のタグとして数える
+        assert_eq!(
+            report.total,
+            CoverageCount {
+                documented: 2,
+                total: 3
+            }
+        );
+        assert_eq!(report.undocumented[0].class_name, "Money");
+    }
+
+    #[test]
+    fn test_coverage_report_empty() {
+        let report = CoverageReport::new(&[], &TagConfig::default(), &[]);
+        assert_eq!(report.total.percentage(), 100.0);
+        assert!(report.meets(100.0));
+    }
+}
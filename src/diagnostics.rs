@@ -0,0 +1,4 @@
+pub mod diagnostic;
+pub mod diagnostic_collector;
+pub mod ubi_doc_error;
+pub mod verbosity;
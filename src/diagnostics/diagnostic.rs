@@ -0,0 +1,49 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::ubi_doc_error::UbiDocError;
+
+/// 診断メッセージ・Lint 結果の重要度
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = ();
+
+    /// as_str で出力した文字列から重要度を戻す
+    fn from_str(severity: &str) -> Result<Self, Self::Err> {
+        match severity {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(()),
+        }
+    }
+}
+
+/// 走査・解析・出力中に発生した問題
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub error: UbiDocError,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity.as_str(), self.error)
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex, MutexGuard, PoisonError,
+};
+
+use super::{
+    diagnostic::{Diagnostic, Severity},
+    ubi_doc_error::UbiDocError,
+    verbosity::Verbosity,
+};
+
+/// 走査・解析・出力で発生した問題と、処理したファイル数を集める
+/// 並列に解析するため、&self のまま追加できるようにしている
+#[derive(Debug, Default)]
+pub struct DiagnosticCollector {
+    diagnostics: Mutex<Vec<Diagnostic>>,
+    files_scanned: AtomicUsize,
+    files_skipped: AtomicUsize,
+    files_failed: AtomicUsize,
+    terms_found: AtomicUsize,
+}
+
+/// 処理結果の集計
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub files_scanned: usize,
+    pub files_skipped: usize,
+    pub files_failed: usize,
+    pub terms_found: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Scanned {} files ({} skipped, {} failed), found {} terms",
+            self.files_scanned, self.files_skipped, self.files_failed, self.terms_found
+        )
+    }
+}
+
+impl DiagnosticCollector {
+    pub fn new() -> Self {
+        DiagnosticCollector::default()
+    }
+
+    /// 問題を記録する (ファイル数には数えない)
+    pub fn report(&self, severity: Severity, error: UbiDocError) {
+        self.lock().push(Diagnostic { severity, error });
+    }
+
+    /// 走査で見つかったファイルを数える
+    pub fn file_scanned(&self) {
+        self.files_scanned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 解析対象外としてスキップしたファイルを記録する
+    pub fn file_skipped(&self, severity: Severity, error: UbiDocError) {
+        self.files_skipped.fetch_add(1, Ordering::Relaxed);
+        self.report(severity, error);
+    }
+
+    /// 読み込み・解析に失敗したファイルを記録する
+    pub fn file_failed(&self, error: UbiDocError) {
+        self.files_failed.fetch_add(1, Ordering::Relaxed);
+        self.report(Severity::Warning, error);
+    }
+
+    /// 見つかった用語の数を加算する
+    pub fn terms_found(&self, count: usize) {
+        self.terms_found.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.lock()
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    #[cfg(test)]
+    pub fn diagnostics(&self) -> MutexGuard<'_, Vec<Diagnostic>> {
+        self.lock()
+    }
+
+    pub fn summary(&self) -> Summary {
+        Summary {
+            files_scanned: self.files_scanned.load(Ordering::Relaxed),
+            files_skipped: self.files_skipped.load(Ordering::Relaxed),
+            files_failed: self.files_failed.load(Ordering::Relaxed),
+            terms_found: self.terms_found.load(Ordering::Relaxed),
+        }
+    }
+
+    /// verbosity に応じて診断メッセージと集計結果を標準エラー出力に書き出す
+    pub fn print(&self, verbosity: Verbosity) {
+        for diagnostic in self.lock().iter() {
+            if verbosity.shows(diagnostic.severity) {
+                eprintln!("{}", diagnostic);
+            }
+        }
+        if verbosity != Verbosity::Quiet {
+            eprintln!("{}", self.summary());
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Diagnostic>> {
+        // 他スレッドが panic しても記録済みのメッセージは使える
+        self.diagnostics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_collector_summary() {
+        let collector = DiagnosticCollector::new();
+        for _ in 0..3 {
+            collector.file_scanned();
+        }
+        collector.file_skipped(
+            Severity::Info,
+            UbiDocError::UnsupportedFile {
+                file_path: "README.md".to_string(),
+            },
+        );
+        collector.file_failed(UbiDocError::Decode {
+            file_path: "Legacy.php".to_string(),
+        });
+        collector.terms_found(2);
+
+        assert_eq!(
+            collector.summary(),
+            Summary {
+                files_scanned: 3,
+                files_skipped: 1,
+                files_failed: 1,
+                terms_found: 2,
+            }
+        );
+        assert_eq!(
+            collector.summary().to_string(),
+            "Scanned 3 files (1 skipped, 1 failed), found 2 terms"
+        );
+        assert_eq!(
+            collector.diagnostics()[1].to_string(),
+            "warning: Legacy.php: skipped file with unsupported encoding"
+        );
+        assert!(!collector.has_errors());
+
+        collector.report(
+            Severity::Error,
+            UbiDocError::Config("Unknown encoding: foo".to_string()),
+        );
+        assert!(collector.has_errors());
+    }
+
+    #[test]
+    fn test_verbosity_shows() {
+        assert!(!Verbosity::Quiet.shows(Severity::Warning));
+        assert!(Verbosity::Quiet.shows(Severity::Error));
+        assert!(!Verbosity::Normal.shows(Severity::Info));
+        assert!(Verbosity::Normal.shows(Severity::Warning));
+        assert!(Verbosity::Verbose.shows(Severity::Info));
+    }
+}
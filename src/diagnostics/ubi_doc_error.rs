@@ -0,0 +1,104 @@
+use std::{fmt, io, path::PathBuf};
+
+/// 走査・解析・出力の各段階で発生するエラー
+#[derive(Debug)]
+pub enum UbiDocError {
+    /// 設定ファイル・CLI 引数の誤り
+    Config(String),
+    /// 入力ディレクトリが存在しない
+    InputNotFound(PathBuf),
+    /// ディレクトリ走査中のエラー (権限不足など)
+    Walk(String),
+    /// 対応していない拡張子のファイル
+    UnsupportedFile { file_path: String },
+    /// 解析対象外の言語のファイル
+    LanguageDisabled {
+        file_path: String,
+        language: &'static str,
+    },
+    /// ファイルを読み込めなかった
+    Read {
+        file_path: String,
+        source: io::Error,
+    },
+    /// NUL 文字を含むためバイナリファイルとみなした
+    Binary { file_path: String },
+    /// UTF-8 / 指定されたどのエンコーディングでもデコードできなかった
+    Decode { file_path: String },
+    /// 構文解析に失敗した
+    Parse { file_path: String, message: String },
+    /// @ubiquitous タグに用語が書かれていない
+    EmptyTerm {
+        file_path: String,
+        line_number: usize,
+    },
+    /// テンプレートの読み込み・描画に失敗した
+    Template(tera::Error),
+    /// JSON への変換に失敗した
+    Serialize(serde_json::Error),
+    /// 出力ファイルを書き込めなかった
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for UbiDocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UbiDocError::Config(message) => write!(f, "{}", message),
+            UbiDocError::InputNotFound(path) => {
+                write!(f, "Input directory not found: {}", path.display())
+            }
+            UbiDocError::Walk(message) => write!(f, "{}", message),
+            UbiDocError::UnsupportedFile { file_path } => {
+                write!(f, "{}: skipped unsupported file", file_path)
+            }
+            UbiDocError::LanguageDisabled {
+                file_path,
+                language,
+            } => write!(
+                f,
+                "{}: skipped {} file (language disabled)",
+                file_path, language
+            ),
+            UbiDocError::Read { file_path, source } => write!(f, "{}: {}", file_path, source),
+            UbiDocError::Binary { file_path } => write!(f, "{}: skipped binary file", file_path),
+            UbiDocError::Decode { file_path } => {
+                write!(f, "{}: skipped file with unsupported encoding", file_path)
+            }
+            UbiDocError::Parse { file_path, message } => {
+                write!(f, "{}: failed to parse: {}", file_path, message)
+            }
+            UbiDocError::EmptyTerm {
+                file_path,
+                line_number,
+            } => write!(f, "{}:{}: empty ubiquitous term", file_path, line_number),
+            UbiDocError::Template(e) => write!(f, "Failed to render template: {}", e),
+            UbiDocError::Serialize(e) => write!(f, "Failed to serialize rows: {}", e),
+            UbiDocError::Write { path, source } => {
+                write!(f, "Failed to write {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UbiDocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UbiDocError::Read { source, .. } | UbiDocError::Write { source, .. } => Some(source),
+            UbiDocError::Template(e) => Some(e),
+            UbiDocError::Serialize(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<tera::Error> for UbiDocError {
+    fn from(e: tera::Error) -> Self {
+        UbiDocError::Template(e)
+    }
+}
+
+impl From<serde_json::Error> for UbiDocError {
+    fn from(e: serde_json::Error) -> Self {
+        UbiDocError::Serialize(e)
+    }
+}
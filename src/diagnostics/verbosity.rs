@@ -0,0 +1,34 @@
+use super::diagnostic::Severity;
+
+/// 診断メッセージの出力レベル
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum Verbosity {
+    /// エラーのみ出力する
+    Quiet,
+    /// 警告・エラーと集計結果を出力する
+    #[default]
+    Normal,
+    /// スキップしたファイルなどの情報もすべて出力する
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(verbose: bool, quiet: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    /// 指定した重要度のメッセージを出力するかどうか
+    pub fn shows(&self, severity: Severity) -> bool {
+        match self {
+            Verbosity::Quiet => severity == Severity::Error,
+            Verbosity::Normal => severity >= Severity::Warning,
+            Verbosity::Verbose => true,
+        }
+    }
+}
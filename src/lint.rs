@@ -0,0 +1,6 @@
+pub mod detect_deprecated_usage;
+pub mod detect_duplicates;
+pub mod detect_unresolved_references;
+pub mod lint_message;
+pub mod normalize_term;
+pub mod run_lint;
@@ -0,0 +1,167 @@
+use crate::diagnostics::diagnostic::Severity;
+use crate::parser::ubiquitous::Ubiquitous;
+
+use super::lint_message::LintMessage;
+
+pub const DEPRECATED_TERM: &str = "deprecated-term";
+
+/// 使用をやめた用語 (@deprecatedTerm) が、ほかの用語の Docコメントで使われていないかを検出する (warning)
+/// 説明・不変条件/業務ルール・使用例・翻訳した説明・設定で追加したタグを調べ、
+/// 用語ごとに最初に見つかった箇所を報告する
+pub fn detect_deprecated_usage(ubiquitous_list: &[Ubiquitous]) -> Vec<LintMessage> {
+    let deprecated_terms: Vec<&Ubiquitous> = ubiquitous_list
+        .iter()
+        .filter(|ubiquitous| ubiquitous.deprecated.is_some() && !ubiquitous.ubiquitous.is_empty())
+        .collect();
+
+    let mut results = Vec::new();
+    for current in ubiquitous_list {
+        let texts = doc_texts(current);
+        for deprecated in deprecated_terms.iter() {
+            if std::ptr::eq(current, *deprecated) {
+                continue;
+            }
+            let Some((field, _)) = texts
+                .iter()
+                .find(|(_, text)| contains_term(text, &deprecated.ubiquitous))
+            else {
+                continue;
+            };
+            let replacement = match deprecated.deprecated.as_deref() {
+                Some(replacement) if !replacement.is_empty() => {
+                    format!("; use \"{}\" instead", replacement)
+                }
+                _ => String::new(),
+            };
+            let message = format!(
+                "{} of \"{}\" uses deprecated term \"{}\"{}",
+                field,
+                current.ubiquitous.trim(),
+                deprecated.ubiquitous.trim(),
+                replacement
+            );
+            results.push(
+                LintMessage::new(Severity::Warning, DEPRECATED_TERM, message).set_location(
+                    current.file_path.clone().unwrap_or_default(),
+                    current.line_number.unwrap_or_default(),
+                ),
+            );
+        }
+    }
+    results
+}
+
+/// 用語の Docコメントのうち、文章として書かれた項目 (項目名と本文)
+fn doc_texts(ubiquitous: &Ubiquitous) -> Vec<(String, &str)> {
+    let mut texts = Vec::new();
+    if let Some(description) = ubiquitous.description.as_deref() {
+        texts.push(("description".to_string(), description));
+    }
+    texts.extend(
+        ubiquitous
+            .rules
+            .iter()
+            .map(|rule| ("rule".to_string(), rule.as_str())),
+    );
+    texts.extend(
+        ubiquitous
+            .examples
+            .iter()
+            .map(|example| ("example".to_string(), example.as_str())),
+    );
+    for (locale, translation) in ubiquitous.translations.iter() {
+        if let Some(description) = translation.description.as_deref() {
+            texts.push((format!("description ({})", locale), description));
+        }
+    }
+    for (name, value) in ubiquitous.extra_fields.iter() {
+        texts.push((format!("@{}", name), value.as_str()));
+    }
+    texts
+}
+
+/// 文章中に用語が含まれているか (大文字小文字を区別しない)
+/// 英数字で始まる・終わる用語は、単語の途中に一致したものを除く (例: "Order" は "Orders" に一致しない)
+fn contains_term(text: &str, term: &str) -> bool {
+    let text = text.to_lowercase();
+    let term = term.trim().to_lowercase();
+    if term.is_empty() {
+        return false;
+    }
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    text.match_indices(&term).any(|(start, _)| {
+        let end = start + term.len();
+        let before_ok = !term.starts_with(is_word_char)
+            || !text[..start].chars().next_back().is_some_and(is_word_char);
+        let after_ok =
+            !term.ends_with(is_word_char) || !text[end..].chars().next().is_some_and(is_word_char);
+        before_ok && after_ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(ubiquitous: &str, description: &str, line: usize) -> Ubiquitous {
+        Ubiquitous::new()
+            .set_ubiquitous(ubiquitous.to_string())
+            .set_description(description.to_string())
+            .set_file_path("src/Order.php".to_string())
+            .set_line_number(line)
+    }
+
+    #[test]
+    fn test_detect_deprecated_usage() {
+        let list = vec![
+            term("Purchase", "old name of order", 1).set_deprecated("Order".to_string()),
+            term("Invoice", "Bill for a purchase.", 10),
+            term("Cart", "Holds purchases before checkout", 20),
+            term("旧注文", "", 30).set_deprecated(String::new()),
+            term("請求", "旧注文に対する請求", 40),
+        ];
+
+        let result = detect_deprecated_usage(&list);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].rule, DEPRECATED_TERM);
+        assert_eq!(result[0].line_number, 10);
+        assert_eq!(
+            result[0].message,
+            "description of \"Invoice\" uses deprecated term \"Purchase\"; use \"Order\" instead"
+        );
+        // 置き換え先がなければ案内しない
+        assert_eq!(result[1].line_number, 40);
+        assert!(!result[1].message.contains("instead"));
+    }
+
+    #[test]
+    fn test_detect_deprecated_usage_other_fields() {
+        let list = vec![
+            term("Purchase", "old name of order", 1).set_deprecated("Order".to_string()),
+            term("Invoice", "", 10).add_rule("Issued once per purchase.".to_string()),
+            term("Cart", "", 20).add_example("Add a purchase to the cart.".to_string()),
+            term("Refund", "", 30)
+                .set_translated_description("en".to_string(), "Returns a purchase.".to_string()),
+            term("Shipment", "", 40)
+                .set_extra_field("status".to_string(), "blocked by purchase".to_string()),
+        ];
+
+        let result = detect_deprecated_usage(&list);
+
+        let messages: Vec<&str> = result.iter().map(|m| m.message.as_str()).collect();
+        assert_eq!(messages.len(), 4);
+        assert!(messages[0].starts_with("rule of \"Invoice\""));
+        assert!(messages[1].starts_with("example of \"Cart\""));
+        assert!(messages[2].starts_with("description (en) of \"Refund\""));
+        assert!(messages[3].starts_with("@status of \"Shipment\""));
+    }
+
+    #[test]
+    fn test_contains_term() {
+        assert!(contains_term("A purchase order", "Purchase"));
+        assert!(!contains_term("Many purchases", "Purchase"));
+        assert!(contains_term("旧注文の一覧", "旧注文"));
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+
+use crate::diagnostics::diagnostic::Severity;
+use crate::parser::ubiquitous::Ubiquitous;
+
+use super::{lint_message::LintMessage, normalize_term::normalize_term};
+
+pub const DUPLICATE_TERM: &str = "duplicate-term";
+pub const TERM_VARIANT: &str = "term-variant";
+pub const TERM_ACROSS_CONTEXTS: &str = "term-across-contexts";
+
+/// 用語の重複・表記揺れ・コンテキスト間での意味の違いを検出する
+/// - 同一コンテキスト内で同じ用語が複数定義されている: error
+/// - 大文字小文字・空白・全角半角だけが異なる表記: warning
+/// - 別コンテキストで同じ用語が異なる説明で定義されている: info (コンテキストマップ作成の参考)
+///
+/// メソッド・列挙子などのメンバーは、同じクラスで宣言されたメンバーどうしでだけ比べる
+/// (OrderStatus と PaymentStatus の両方に Pending があっても重複としない)
+pub fn detect_duplicates(ubiquitous_list: &[Ubiquitous]) -> Vec<LintMessage> {
+    // 宣言しているクラス (メンバーの場合) と正規化した用語ごとにグルーピング
+    let mut groups: BTreeMap<(Option<&str>, String), Vec<&Ubiquitous>> = BTreeMap::new();
+    for ubiquitous in ubiquitous_list {
+        let term = normalize_term(&ubiquitous.ubiquitous);
+        if term.is_empty() {
+            continue;
+        }
+        let parent = ubiquitous
+            .kind
+            .is_some_and(|kind| kind.is_member())
+            .then_some(ubiquitous.parent.as_deref())
+            .flatten();
+        groups.entry((parent, term)).or_default().push(ubiquitous);
+    }
+
+    let mut results = Vec::new();
+    for entries in groups.values().filter(|entries| entries.len() > 1) {
+        for (i, current) in entries.iter().enumerate() {
+            let others = entries
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| *other);
+            results.extend(compare_with_others(current, others));
+        }
+    }
+
+    results.sort_by(|a, b| {
+        (&a.file_path, a.line_number, a.level).cmp(&(&b.file_path, b.line_number, b.level))
+    });
+    results
+}
+
+fn compare_with_others<'a>(
+    current: &Ubiquitous,
+    others: impl Iterator<Item = &'a Ubiquitous>,
+) -> Vec<LintMessage> {
+    let current_context = normalized_context(current);
+
+    let mut duplicates = Vec::new();
+    let mut variants = Vec::new();
+    let mut other_contexts = Vec::new();
+
+    for other in others {
+        if current.ubiquitous.trim() != other.ubiquitous.trim() {
+            variants.push(format!(
+                "\"{}\" at {}",
+                other.ubiquitous.trim(),
+                location(other)
+            ));
+        }
+
+        if normalized_context(other) == current_context {
+            duplicates.push(other);
+        } else if current.description != other.description {
+            other_contexts.push(format!(
+                "\"{}\" at {}",
+                other.context.clone().unwrap_or_default(),
+                location(other)
+            ));
+        }
+    }
+
+    let mut results = Vec::new();
+
+    if !duplicates.is_empty() {
+        let conflicting = duplicates
+            .iter()
+            .any(|other| other.description != current.description);
+        let message = format!(
+            "term \"{}\" is defined more than once in context \"{}\"{} (also at {})",
+            current.ubiquitous.trim(),
+            current.context.clone().unwrap_or_default(),
+            if conflicting {
+                " with conflicting descriptions"
+            } else {
+                ""
+            },
+            duplicates
+                .iter()
+                .map(|other| location(other))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        results.push(with_location(
+            LintMessage::new(Severity::Error, DUPLICATE_TERM, message),
+            current,
+        ));
+    }
+
+    if !variants.is_empty() {
+        let message = format!(
+            "term \"{}\" has spelling variants: {}",
+            current.ubiquitous.trim(),
+            variants.join(", ")
+        );
+        results.push(with_location(
+            LintMessage::new(Severity::Warning, TERM_VARIANT, message),
+            current,
+        ));
+    }
+
+    if !other_contexts.is_empty() {
+        let message = format!(
+            "term \"{}\" has a different meaning in other contexts: {}",
+            current.ubiquitous.trim(),
+            other_contexts.join(", ")
+        );
+        results.push(with_location(
+            LintMessage::new(Severity::Info, TERM_ACROSS_CONTEXTS, message),
+            current,
+        ));
+    }
+
+    results
+}
+
+fn normalized_context(ubiquitous: &Ubiquitous) -> String {
+    normalize_term(ubiquitous.context.as_deref().unwrap_or_default())
+}
+
+fn location(ubiquitous: &Ubiquitous) -> String {
+    format!(
+        "{}:{}",
+        ubiquitous.file_path.clone().unwrap_or_default(),
+        ubiquitous.line_number.unwrap_or_default()
+    )
+}
+
+fn with_location(message: LintMessage, ubiquitous: &Ubiquitous) -> LintMessage {
+    message.set_location(
+        ubiquitous.file_path.clone().unwrap_or_default(),
+        ubiquitous.line_number.unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::class_doc::DeclarationKind;
+
+    fn term(ubiquitous: &str, context: &str, description: &str, line: usize) -> Ubiquitous {
+        Ubiquitous::new()
+            .set_ubiquitous(ubiquitous.to_string())
+            .set_context(context.to_string())
+            .set_description(description.to_string())
+            .set_file_path("src/Order.php".to_string())
+            .set_line_number(line)
+    }
+
+    #[test]
+    fn test_detect_duplicates_none() {
+        let list = vec![
+            term("Order", "Sales", "purchase order", 1),
+            term("Invoice", "Sales", "bill", 10),
+        ];
+        assert!(detect_duplicates(&list).is_empty());
+    }
+
+    #[test]
+    fn test_detect_duplicates_same_context() {
+        let list = vec![
+            term("Order", "Sales", "purchase order", 1),
+            term("Order", "Sales", "another meaning", 10),
+        ];
+        let result = detect_duplicates(&list);
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|m| m.level == Severity::Error));
+        assert!(result.iter().all(|m| m.rule == DUPLICATE_TERM));
+        assert!(result[0].message.contains("conflicting descriptions"));
+        assert!(result[0].message.contains("src/Order.php:10"));
+        assert_eq!(result[1].line_number, 10);
+    }
+
+    #[test]
+    fn test_detect_duplicates_variants() {
+        let list = vec![
+            term("Order Line", "Sales", "line", 1),
+            term("ＯｒｄｅｒＬｉｎｅ", "Shipping", "line", 10),
+        ];
+        let result = detect_duplicates(&list);
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|m| m.level == Severity::Warning));
+        assert!(result[0].message.contains("ＯｒｄｅｒＬｉｎｅ"));
+    }
+
+    #[test]
+    fn test_detect_duplicates_across_contexts() {
+        let list = vec![
+            term("Order", "Sales", "purchase order", 1),
+            term("Order", "Shipping", "shipment request", 10),
+        ];
+        let result = detect_duplicates(&list);
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|m| m.level == Severity::Info));
+        assert!(result[0].message.contains("\"Shipping\""));
+    }
+
+    #[test]
+    fn test_detect_duplicates_enum_cases() {
+        let case = |parent: &str, line: usize| {
+            term("Pending", "Sales", "waiting", line)
+                .set_kind(DeclarationKind::EnumCase)
+                .set_parent(parent.to_string())
+        };
+        let list = vec![
+            case("OrderStatus", 1),
+            case("PaymentStatus", 10),
+            term("Pending", "Sales", "waiting", 20),
+        ];
+        // 別の enum の列挙子や、クラスの用語とは重複としない
+        assert!(detect_duplicates(&list).is_empty());
+
+        let list = vec![case("OrderStatus", 1), case("OrderStatus", 10)];
+        let result = detect_duplicates(&list);
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|m| m.rule == DUPLICATE_TERM));
+    }
+
+    #[test]
+    fn test_detect_duplicates_same_meaning_across_contexts() {
+        let list = vec![
+            term("Order", "Sales", "purchase order", 1),
+            term("Order", "Shipping", "purchase order", 10),
+        ];
+        assert!(detect_duplicates(&list).is_empty());
+    }
+}
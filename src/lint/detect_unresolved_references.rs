@@ -0,0 +1,90 @@
+use crate::diagnostics::diagnostic::Severity;
+use crate::parser::{
+    term_index::TermIndex,
+    term_reference::{inline_references, is_code_reference},
+    ubiquitous::Ubiquitous,
+};
+
+use super::lint_message::LintMessage;
+
+pub const UNRESOLVED_REFERENCE: &str = "unresolved-reference";
+
+/// @see / @related と説明文中の {@link Term} / [[Term]] のうち、
+/// 用語集に存在しない用語への参照を検出する (warning)
+/// Javadoc の @see Foo#bar() や {@link java.util.List} のようにコードを指す参照は、
+/// 用語として見つからなくても報告しない
+pub fn detect_unresolved_references(ubiquitous_list: &[Ubiquitous]) -> Vec<LintMessage> {
+    let term_index = TermIndex::new(ubiquitous_list);
+
+    let mut results = Vec::new();
+    for ubiquitous in ubiquitous_list {
+        let inline = ubiquitous
+            .description
+            .as_deref()
+            .map(inline_references)
+            .unwrap_or_default();
+        for reference in ubiquitous.related.iter().map(String::as_str).chain(inline) {
+            if term_index
+                .resolve(reference, ubiquitous.context.as_deref())
+                .is_some()
+                || is_code_reference(reference)
+            {
+                continue;
+            }
+            let message = format!(
+                "term \"{}\" references unknown term \"{}\"",
+                ubiquitous.ubiquitous.trim(),
+                reference
+            );
+            results.push(
+                LintMessage::new(Severity::Warning, UNRESOLVED_REFERENCE, message).set_location(
+                    ubiquitous.file_path.clone().unwrap_or_default(),
+                    ubiquitous.line_number.unwrap_or_default(),
+                ),
+            );
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_unresolved_references() {
+        let list = vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_context("Sales".to_string())
+                .set_description(
+                    "Has one or more [[Order Line]]s for a {@link Customer}".to_string(),
+                )
+                .add_related("Billing:Invoice".to_string())
+                .add_related("Order Line#quantity".to_string())
+                .add_related("java.util.List".to_string())
+                .add_related("Billing::Invoice".to_string())
+                .set_file_path("src/Order.php".to_string())
+                .set_line_number(3),
+            Ubiquitous::new()
+                .set_ubiquitous("Order Line".to_string())
+                .set_context("Sales".to_string())
+                .add_related("Order".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Invoice".to_string())
+                .set_context("Sales".to_string()),
+        ];
+
+        let result = detect_unresolved_references(&list);
+
+        // Invoice は Billing コンテキストにはないので未解決
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|m| m.rule == UNRESOLVED_REFERENCE));
+        assert_eq!(
+            result[0].message,
+            "term \"Order\" references unknown term \"Billing:Invoice\""
+        );
+        assert_eq!(result[0].line_number, 3);
+        assert!(result[1].message.contains("\"Customer\""));
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+
+use crate::diagnostics::diagnostic::Severity;
+
+/// Lint の検出結果1件分
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintMessage {
+    pub level: Severity,
+    pub rule: String,
+    pub message: String,
+    pub file_path: String,
+    pub line_number: usize,
+}
+
+impl LintMessage {
+    pub fn new(level: Severity, rule: &str, message: String) -> Self {
+        LintMessage {
+            level,
+            rule: rule.to_string(),
+            message,
+            file_path: String::new(),
+            line_number: 0,
+        }
+    }
+
+    pub fn set_location(mut self, file_path: String, line_number: usize) -> Self {
+        self.file_path = file_path;
+        self.line_number = line_number;
+        self
+    }
+}
+
+impl fmt::Display for LintMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {} ({}:{})",
+            self.level.as_str(),
+            self.rule,
+            self.message,
+            self.file_path,
+            self.line_number
+        )
+    }
+}
@@ -0,0 +1,38 @@
+/// 表記揺れを吸収するために用語を正規化する
+/// - 全角英数字・記号を半角に変換
+/// - 空白（全角スペースを含む）を除去
+/// - 小文字に統一
+pub fn normalize_term(term: &str) -> String {
+    term.chars()
+        .map(to_half_width)
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 全角の ASCII 相当文字 (U+FF01〜U+FF5E) と全角スペースを半角に変換する
+fn to_half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_term_case_and_whitespace() {
+        assert_eq!(normalize_term("Order"), "order");
+        assert_eq!(normalize_term(" ORDER  LINE "), "orderline");
+        assert_eq!(normalize_term("Order\u{3000}Line"), "orderline");
+    }
+
+    #[test]
+    fn test_normalize_term_full_width() {
+        assert_eq!(normalize_term("Ｏｒｄｅｒ"), "order");
+        assert_eq!(normalize_term("注文１"), "注文1");
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use crate::config::ubi_doc_config::LintRuleLevel;
+use crate::parser::ubiquitous::Ubiquitous;
+
+use super::{
+    detect_deprecated_usage::detect_deprecated_usage, detect_duplicates::detect_duplicates,
+    detect_unresolved_references::detect_unresolved_references, lint_message::LintMessage,
+};
+
+/// すべての Lint ルールを実行し、検出結果をまとめて返す
+/// rule_levels で指定されたルールは重要度を上書きし、off のルールは結果から除外する
+pub fn run_lint(
+    ubiquitous_list: &[Ubiquitous],
+    rule_levels: &BTreeMap<String, LintRuleLevel>,
+) -> Vec<LintMessage> {
+    let mut results = Vec::new();
+    results.extend(detect_duplicates(ubiquitous_list));
+    results.extend(detect_deprecated_usage(ubiquitous_list));
+    results.extend(detect_unresolved_references(ubiquitous_list));
+
+    results
+        .into_iter()
+        .filter_map(
+            |mut lint_message| match rule_levels.get(&lint_message.rule) {
+                Some(rule_level) => rule_level.to_severity().map(|level| {
+                    lint_message.level = level;
+                    lint_message
+                }),
+                None => Some(lint_message),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::diagnostic::Severity;
+    use crate::lint::detect_duplicates::{DUPLICATE_TERM, TERM_VARIANT};
+
+    fn term(ubiquitous: &str, line: usize) -> Ubiquitous {
+        Ubiquitous::new()
+            .set_ubiquitous(ubiquitous.to_string())
+            .set_context("Sales".to_string())
+            .set_file_path("src/Order.php".to_string())
+            .set_line_number(line)
+    }
+
+    #[test]
+    fn test_run_lint_rule_levels() {
+        let list = vec![term("Order", 1), term("order", 10)];
+
+        let results = run_lint(&list, &BTreeMap::new());
+        assert_eq!(results.len(), 4);
+
+        let rule_levels = BTreeMap::from([
+            (DUPLICATE_TERM.to_string(), LintRuleLevel::Warning),
+            (TERM_VARIANT.to_string(), LintRuleLevel::Off),
+        ]);
+        let results = run_lint(&list, &rule_levels);
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|m| m.rule == DUPLICATE_TERM && m.level == Severity::Warning));
+    }
+}
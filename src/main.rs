@@ -1,56 +1,451 @@
 use clap::Parser;
 use outputs::html::{
     generate_html::generate_html, ubiquitous_row::UbiquitousRow, ubiquitous_rows::UbiquitousRows,
+    ui_messages::UiMessages,
 };
-use std::path::Path;
+use outputs::{
+    graph::{
+        context_map::ContextMap, generate_context_map::generate_context_map,
+        generate_dot::generate_dot, generate_mermaid::generate_mermaid,
+    },
+    json::generate_json::generate_json,
+    markdown::generate_markdown::generate_markdown,
+};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use parser::get_ubiquitous_list::get_ubiquitous_list;
+use config::{
+    load_config::{load_config, CONFIG_FILE_NAME},
+    ubi_doc_config::{LinkConfig, LinkProvider, OutputFormat, UbiDocConfig, DEFAULT_INPUT},
+};
+use coverage::coverage_report::CoverageReport;
+use diagnostics::{
+    diagnostic::Severity, diagnostic_collector::DiagnosticCollector, ubi_doc_error::UbiDocError,
+    verbosity::Verbosity,
+};
+use lint::run_lint::run_lint;
+use parser::{
+    class_doc::DeclarationKind,
+    context_resolver::ContextResolver,
+    file_io::walk_options::WalkOptions,
+    get_class_declaration_list::get_class_declaration_list,
+    get_ubiquitous_list::get_ubiquitous_list,
+    owner_resolver::{inherit_teams, OwnerResolver},
+    term_index::TermIndex,
+};
+mod config;
+mod coverage;
+mod diagnostics;
+mod lint;
 mod outputs;
 mod parser;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "ユビキタス言語表作成ツール", long_about = None)]
 struct Args {
-    /// 入力ディレクトリのパス
-    #[arg(short, long, default_value = "sample")]
-    input: String,
+    /// 設定ファイルのパス (未指定の場合は入力ディレクトリ直下の ubi-doc.toml を使用)
+    #[arg(short, long)]
+    config: Option<String>,
+
+    /// 入力ディレクトリのパス (複数指定可)
+    #[arg(short, long)]
+    input: Vec<String>,
+
+    /// 出力ディレクトリのパス
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// 走査対象に含めるファイルの glob (入力ディレクトリからの相対パス、複数指定可)
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// 走査対象から除外するファイル・ディレクトリの glob (複数指定可)
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// .gitignore / .ignore を無視してすべてのファイルを走査する
+    #[arg(long, overrides_with = "ignore")]
+    no_ignore: bool,
+
+    /// .gitignore / .ignore に従う (設定ファイルの respect-ignore-files = false を打ち消す)
+    #[arg(long, overrides_with = "no_ignore")]
+    ignore: bool,
+
+    /// シンボリックリンクをたどる
+    #[arg(long, overrides_with = "no_follow_symlinks")]
+    follow_symlinks: bool,
+
+    /// シンボリックリンクをたどらない (設定ファイルの follow-symlinks = true を打ち消す)
+    #[arg(long, overrides_with = "follow_symlinks")]
+    no_follow_symlinks: bool,
+
+    /// UTF-8 でデコードできなかった場合に試すエンコーディング (例: Shift_JIS。複数指定可)
+    #[arg(long = "encoding")]
+    encodings: Vec<String>,
+
+    /// 解析対象の言語 (php, kotlin, ruby, java。複数指定可)
+    #[arg(long = "language")]
+    languages: Vec<String>,
+
+    /// 出力形式 (複数指定可)
+    #[arg(long = "format", value_enum)]
+    formats: Vec<OutputFormat>,
+
+    /// HTML テンプレートのディレクトリ
+    #[arg(long)]
+    template_dir: Option<String>,
+
+    /// @owner がない用語の担当チームを決める CODEOWNERS ファイル
+    #[arg(long)]
+    codeowners: Option<String>,
+
+    /// 指定したチームが担当する用語だけを出力する (例: @acme/sales。複数指定可)
+    #[arg(long = "owner")]
+    owners: Vec<String>,
+
+    /// HTML の画面の言語 (en, ja。ほかの言語は --ui-catalog と一緒に指定)
+    #[arg(long)]
+    ui_lang: Option<String>,
+
+    /// HTML の画面の文言のカタログファイル (TOML)
+    #[arg(long)]
+    ui_catalog: Option<String>,
+
+    /// HTML に用語の関連図を埋め込むときに読み込む Mermaid のスクリプト (URL またはファイル)
+    #[arg(long)]
+    mermaid_script: Option<String>,
+
+    /// ソースコードへのリンク先
+    #[arg(long, value_enum)]
+    link_provider: Option<LinkProvider>,
+
+    /// リポジトリ (例: owner/repo)
+    #[arg(long)]
+    repo: Option<String>,
+
+    /// ブランチ名
+    #[arg(long)]
+    branch: Option<String>,
+
+    /// Lint のみ実行し、エラーがあれば終了コード 1 を返す
+    #[arg(long)]
+    lint: bool,
+
+    /// ドメインクラスの用語集カバレッジを出力する
+    #[arg(long)]
+    coverage: bool,
+
+    /// カバレッジ計測の対象ディレクトリ (入力ディレクトリからの相対パス、複数指定可)
+    #[arg(long = "domain-path")]
+    domain_paths: Vec<String>,
+
+    /// カバレッジに数える宣言の種類 (class, interface, enum, record, trait, object, module。
+    /// 複数指定可、未指定の場合はすべて)
+    #[arg(long = "coverage-kind")]
+    coverage_kinds: Vec<DeclarationKind>,
+
+    /// カバレッジの最低値(%)。下回った場合は終了コード 1 を返す
+    #[arg(long)]
+    min_coverage: Option<f64>,
+
+    /// スキップしたファイルなどの詳細も出力する
+    #[arg(short, long, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// エラー以外のメッセージを出力しない
+    #[arg(short, long)]
+    quiet: bool,
+}
 
-    /// 出力HTMLファイルのパス
-    #[arg(short, long, default_value = "docs")]
-    output: String,
+impl Args {
+    /// CLI 引数で指定された項目だけを持つ設定を作成する
+    fn to_config(&self) -> UbiDocConfig {
+        UbiDocConfig {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            respect_ignore_files: flag(self.ignore, self.no_ignore),
+            follow_symlinks: flag(self.follow_symlinks, self.no_follow_symlinks),
+            encodings: self.encodings.clone(),
+            languages: self.languages.clone(),
+            formats: self.formats.clone(),
+            template_dir: self.template_dir.clone(),
+            codeowners: self.codeowners.clone(),
+            ui_lang: self.ui_lang.clone(),
+            ui_catalog: self.ui_catalog.clone(),
+            mermaid_script: self.mermaid_script.clone(),
+            link: LinkConfig {
+                provider: self.link_provider,
+                repo: self.repo.clone(),
+                branch: self.branch.clone(),
+                base_url: None,
+            },
+            ..UbiDocConfig::default()
+        }
+    }
+}
+
+/// --foo / --no-foo の組を設定値にする (どちらも指定されていなければ設定ファイルの値を使う)
+fn flag(enabled: bool, disabled: bool) -> Option<bool> {
+    if enabled {
+        Some(true)
+    } else if disabled {
+        Some(false)
+    } else {
+        None
+    }
 }
 
-fn main() {
+fn main() -> ExitCode {
     // コマンドライン引数のパース
     let args = Args::parse();
+    let verbosity = Verbosity::from_flags(args.verbose, args.quiet);
+    let diagnostics = DiagnosticCollector::new();
 
-    // 入力ディレクトリのパスを取得
-    let input_path = Path::new(&args.input);
-    let ubiquitous_list = get_ubiquitous_list(input_path);
+    // 設定ファイルを読み込み、CLI 引数で上書きする
+    // 設定ファイルは最初の入力ディレクトリから探す
+    let input_root = args.input.first().map_or(DEFAULT_INPUT, String::as_str);
+    let file_config =
+        match load_config(args.config.as_deref().map(Path::new), Path::new(input_root)) {
+            Ok(config) => config,
+            Err(e) => return abort(&diagnostics, verbosity, e),
+        };
+    // 2つ目以降の入力ディレクトリの ubi-doc.toml は読み込まないことを知らせる
+    if args.config.is_none() {
+        for input in args.input.iter().skip(1) {
+            let ignored = Path::new(input).join(CONFIG_FILE_NAME);
+            if ignored.is_file() {
+                diagnostics.report(
+                    Severity::Warning,
+                    UbiDocError::Config(format!(
+                        "Config file {:?} is ignored; only the first input directory is searched (use --config to share one file)",
+                        ignored
+                    )),
+                );
+            }
+        }
+    }
+    let mut config = file_config.override_with(args.to_config());
+    if config.link.repo.is_none() {
+        config.link.repo = std::env::var("GITHUB_REPOSITORY").ok();
+    }
+    if config.link.branch.is_none() {
+        config.link.branch = std::env::var("GITHUB_REF_NAME").ok();
+    }
 
-    // 出力ファイルのパスを取得
-    let output_path = Path::new(&args.output);
+    let walk_options = match WalkOptions::from_config(&config) {
+        Ok(walk_options) => walk_options,
+        Err(e) => return abort(&diagnostics, verbosity, e),
+    };
+
+    let context_resolver = match ContextResolver::from_config(&config) {
+        Ok(context_resolver) => context_resolver,
+        Err(e) => return abort(&diagnostics, verbosity, e),
+    };
+
+    if args.coverage {
+        let exit_code = report_coverage(&args, &config, &walk_options, &diagnostics);
+        return finish(&diagnostics, verbosity, exit_code);
+    }
+
+    // 入力ディレクトリごとに用語を収集し、@context がなければディレクトリのコンテキストを設定
+    let ubiquitous_list: Vec<_> = config
+        .input_paths()
+        .iter()
+        .flat_map(|input_path| {
+            get_ubiquitous_list(Path::new(input_path), &config, &walk_options, &diagnostics)
+        })
+        .collect();
+    let ubiquitous_list = context_resolver.apply(ubiquitous_list);
+    // @owner がなければ、宣言しているクラスの担当チーム、CODEOWNERS の順に担当チームを設定
+    let ubiquitous_list = inherit_teams(ubiquitous_list);
+    // CODEOWNERS を読み込めなければエラーとして記録し、担当チームなしで出力を続ける
+    let ubiquitous_list = match OwnerResolver::from_config(&config) {
+        Ok(owner_resolver) => owner_resolver.apply(ubiquitous_list),
+        Err(e) => {
+            diagnostics.report(Severity::Error, e);
+            ubiquitous_list
+        }
+    };
+
+    // 用語の重複・表記揺れなどをチェック
+    let lint_messages = run_lint(&ubiquitous_list, &config.lint);
+    for lint_message in lint_messages.iter() {
+        if verbosity.shows(lint_message.level) {
+            eprintln!("{}", lint_message);
+        }
+    }
+    if args.lint {
+        let has_error = lint_messages
+            .iter()
+            .any(|lint_message| lint_message.level == Severity::Error);
+        let exit_code = if has_error {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        };
+        return finish(&diagnostics, verbosity, exit_code);
+    }
+
+    // @see や {@link Term} の参照先を探すための索引
+    // --owner で出力しない用語も登録しておき、その用語への参照はリンクにせず用語名だけを表示する
+    let mut term_index = TermIndex::new(&ubiquitous_list);
 
-    let repo: String =
-        std::env::var("GITHUB_REPOSITORY").unwrap_or_else(|_| "Glider2355/ubi-doc".to_string());
-    let branch: String = std::env::var("GITHUB_REF_NAME").unwrap_or_else(|_| "main".to_string());
+    // --owner が指定されていれば、そのチームが担当する用語だけを出力する
+    let mut anchors = Vec::new();
+    let mut exported_list = Vec::new();
+    for (index, ubiquitous) in ubiquitous_list.into_iter().enumerate() {
+        if args.owners.is_empty() || args.owners.iter().any(|team| ubiquitous.is_owned_by(team)) {
+            anchors.push(term_index.anchor(index).to_string());
+            exported_list.push(ubiquitous);
+        } else {
+            term_index.hide(index);
+        }
+    }
+    let ubiquitous_list = exported_list;
+
+    // 出力ファイルのパスを取得
+    let output_path = PathBuf::from(config.output_path());
 
     let mut ubiquitous_rows = UbiquitousRows::new();
-    for ubiquitous in ubiquitous_list.iter() {
-        let row = UbiquitousRow::new()
+    for (ubiquitous, anchor) in ubiquitous_list.iter().zip(anchors) {
+        let mut row = UbiquitousRow::new()
+            .set_anchor(anchor)
             .set_class_name(ubiquitous.class_name.clone().unwrap_or_default())
+            .set_qualified_name(ubiquitous.qualified_name.clone().unwrap_or_default())
+            .set_namespace(ubiquitous.namespace.clone().unwrap_or_default())
+            .set_kind(
+                ubiquitous
+                    .kind
+                    .map(|kind| kind.as_str().to_string())
+                    .unwrap_or_default(),
+            )
+            .set_parent(ubiquitous.parent.clone().unwrap_or_default())
             .set_ubiquitous(ubiquitous.ubiquitous.clone())
             .set_context(ubiquitous.context.clone().unwrap_or_default())
             .set_description(ubiquitous.description.clone().unwrap_or_default())
-            .set_github_url(
-                repo.clone(),
-                branch.clone(),
+            .set_aliases(ubiquitous.aliases.clone())
+            .set_rules(ubiquitous.rules.clone())
+            .set_examples(ubiquitous.examples.clone())
+            .set_deprecated(ubiquitous.deprecated.clone())
+            .set_references(&term_index, &ubiquitous.related)
+            .set_translations(&term_index, &ubiquitous.translations)
+            .set_teams(ubiquitous.teams.clone())
+            .set_extra_fields(ubiquitous.extra_fields.clone())
+            .set_stereotype(ubiquitous.stereotype)
+            .set_aggregate_root(&term_index, ubiquitous.aggregate.as_deref())
+            .set_source_link(
+                &config.link,
                 ubiquitous.file_path.clone().unwrap_or_default(),
                 ubiquitous.line_number.unwrap_or_default(),
             );
+        for lint_message in lint_messages.iter().filter(|lint_message| {
+            Some(&lint_message.file_path) == ubiquitous.file_path.as_ref()
+                && Some(lint_message.line_number) == ubiquitous.line_number
+        }) {
+            row = row.add_lint_message(lint_message);
+        }
         ubiquitous_rows.add(row);
     }
+    ubiquitous_rows.sort();
+    ubiquitous_rows.nest_enum_cases();
+    ubiquitous_rows.group_by_aggregate();
+
+    // 指定された形式で出力
+    let template_dir = config.template_dir.as_ref().map(Path::new);
+    for format in config.output_formats() {
+        let result = match format {
+            // 画面の文言は HTML を出力するときだけ読み込む
+            OutputFormat::Html => UiMessages::from_config(&config).and_then(|ui_messages| {
+                generate_html(
+                    &ubiquitous_rows,
+                    &output_path,
+                    template_dir,
+                    &ui_messages,
+                    &config.tags.extra,
+                    config.mermaid_script.as_deref(),
+                )
+            }),
+            OutputFormat::Json => generate_json(&ubiquitous_rows, &output_path),
+            OutputFormat::Markdown => {
+                generate_markdown(&ubiquitous_rows, &output_path, &config.tags.extra)
+            }
+            OutputFormat::Dot => generate_dot(&ubiquitous_rows, &output_path),
+            OutputFormat::Mermaid => generate_mermaid(&ubiquitous_rows, &output_path),
+            OutputFormat::ContextMap => {
+                generate_context_map(&ContextMap::new(&ubiquitous_list), &output_path)
+            }
+        };
+        if let Err(e) = result {
+            diagnostics.report(Severity::Error, e);
+        }
+    }
+
+    finish(&diagnostics, verbosity, ExitCode::SUCCESS)
+}
+
+/// 診断メッセージと集計結果を出力し、エラーがあれば終了コード 1 を返す
+fn finish(
+    diagnostics: &DiagnosticCollector,
+    verbosity: Verbosity,
+    exit_code: ExitCode,
+) -> ExitCode {
+    diagnostics.print(verbosity);
+    if diagnostics.has_errors() {
+        ExitCode::FAILURE
+    } else {
+        exit_code
+    }
+}
+
+/// 処理を続けられないエラー (設定の誤りなど) を記録して終了する
+fn abort(diagnostics: &DiagnosticCollector, verbosity: Verbosity, error: UbiDocError) -> ExitCode {
+    diagnostics.report(Severity::Error, error);
+    finish(diagnostics, verbosity, ExitCode::FAILURE)
+}
 
-    // HTMLファイルとして出力
-    generate_html(ubiquitous_rows, output_path);
+/// ドメインクラスの用語集カバレッジを出力する
+fn report_coverage(
+    args: &Args,
+    config: &UbiDocConfig,
+    walk_options: &WalkOptions,
+    diagnostics: &DiagnosticCollector,
+) -> ExitCode {
+    let domain_paths: Vec<PathBuf> = config
+        .input_paths()
+        .iter()
+        .flat_map(|input_path| {
+            let input_path = Path::new(input_path);
+            if args.domain_paths.is_empty() {
+                vec![input_path.to_path_buf()]
+            } else {
+                args.domain_paths
+                    .iter()
+                    .map(|domain_path| input_path.join(domain_path))
+                    .collect()
+            }
+        })
+        .collect();
+
+    let declarations: Vec<_> = domain_paths
+        .iter()
+        .flat_map(|domain_path| {
+            get_class_declaration_list(domain_path, config, walk_options, diagnostics)
+        })
+        .collect();
+    let report = CoverageReport::new(&declarations, &config.tags, &args.coverage_kinds);
+    print!("{}", report.render());
+
+    match args.min_coverage {
+        Some(min_coverage) if !report.meets(min_coverage) => {
+            eprintln!(
+                "Coverage {:.1}% is below the minimum {:.1}%",
+                report.total.percentage(),
+                min_coverage
+            );
+            ExitCode::FAILURE
+        }
+        _ => ExitCode::SUCCESS,
+    }
 }
@@ -1 +1,4 @@
+pub mod graph;
 pub mod html;
+pub mod json;
+pub mod markdown;
@@ -0,0 +1,5 @@
+pub mod context_map;
+pub mod generate_context_map;
+pub mod generate_dot;
+pub mod generate_mermaid;
+pub mod term_graph;
@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+
+use crate::lint::normalize_term::normalize_term;
+use crate::parser::{type_references::simple_type_name, ubiquitous::Ubiquitous};
+
+/// コンテキスト間の依存関係 (コンテキストマップ)
+/// あるコンテキストのクラスが、別のコンテキストの用語のクラスを型として使っていれば依存とみなす
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ContextMap {
+    /// @context に書かれたすべてのコンテキスト (名前順)
+    pub contexts: Vec<String>,
+    /// コンテキストごとの依存先 (隣接リスト)
+    pub adjacency: BTreeMap<String, Vec<ContextDependency>>,
+    /// 複数のコンテキストで定義されている用語
+    pub shared_terms: Vec<SharedTerm>,
+}
+
+/// 依存先のコンテキストと、その中で使われている用語
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ContextDependency {
+    pub context: String,
+    pub terms: Vec<String>,
+}
+
+/// 複数のコンテキストで定義されている用語
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SharedTerm {
+    pub term: String,
+    pub contexts: Vec<String>,
+}
+
+impl ContextMap {
+    /// コンテキストのない用語と、メソッドなどのメンバーの用語は対象外
+    pub fn new(ubiquitous_list: &[Ubiquitous]) -> Self {
+        let terms: Vec<(&Ubiquitous, &str)> = ubiquitous_list
+            .iter()
+            .filter_map(|ubiquitous| {
+                let context = ubiquitous.context.as_deref()?.trim();
+                (!context.is_empty()).then_some((ubiquitous, context))
+            })
+            .collect();
+
+        let contexts: BTreeSet<&str> = terms.iter().map(|(_, context)| *context).collect();
+
+        // 型名から用語を引くための索引 (完全修飾名とクラス名)
+        let mut by_qualified_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut by_class_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, (ubiquitous, _)) in terms.iter().enumerate() {
+            if ubiquitous.kind.is_some_and(|kind| kind.is_member()) {
+                continue;
+            }
+            if let Some(qualified_name) = &ubiquitous.qualified_name {
+                by_qualified_name
+                    .entry(qualified_name)
+                    .or_default()
+                    .push(index);
+            }
+            if let Some(class_name) = &ubiquitous.class_name {
+                by_class_name.entry(class_name).or_default().push(index);
+            }
+        }
+
+        let mut dependencies: BTreeMap<&str, BTreeMap<&str, BTreeSet<&str>>> = BTreeMap::new();
+        for (ubiquitous, context) in terms.iter() {
+            for type_name in ubiquitous.type_references.iter() {
+                let qualified_name = type_name.trim_start_matches(['\\', ':']);
+                let Some(candidates) = by_qualified_name
+                    .get(qualified_name)
+                    .or_else(|| by_class_name.get(simple_type_name(type_name)))
+                else {
+                    continue;
+                };
+                // 同じコンテキストにその型の用語があれば、コンテキスト内の参照とみなす
+                if candidates.iter().any(|index| terms[*index].1 == *context) {
+                    continue;
+                }
+                let (target, target_context) = terms[candidates[0]];
+                dependencies
+                    .entry(context)
+                    .or_default()
+                    .entry(target_context)
+                    .or_default()
+                    .insert(target.ubiquitous.trim());
+            }
+        }
+
+        let adjacency = contexts
+            .iter()
+            .map(|context| {
+                let targets = dependencies
+                    .get(context)
+                    .map(|targets| {
+                        targets
+                            .iter()
+                            .map(|(target_context, terms)| ContextDependency {
+                                context: target_context.to_string(),
+                                terms: terms.iter().map(|term| term.to_string()).collect(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (context.to_string(), targets)
+            })
+            .collect();
+
+        ContextMap {
+            contexts: contexts.iter().map(|context| context.to_string()).collect(),
+            adjacency,
+            shared_terms: shared_terms(&terms),
+        }
+    }
+}
+
+/// 表記揺れを吸収した同じ用語が、2つ以上のコンテキストで定義されているものを返す
+fn shared_terms(terms: &[(&Ubiquitous, &str)]) -> Vec<SharedTerm> {
+    let mut groups: BTreeMap<String, (&str, BTreeSet<&str>)> = BTreeMap::new();
+    for (ubiquitous, context) in terms {
+        let term = ubiquitous.ubiquitous.trim();
+        if term.is_empty() {
+            continue;
+        }
+        groups
+            .entry(normalize_term(term))
+            .or_insert_with(|| (term, BTreeSet::new()))
+            .1
+            .insert(context);
+    }
+    groups
+        .into_values()
+        .filter(|(_, contexts)| contexts.len() > 1)
+        .map(|(term, contexts)| SharedTerm {
+            term: term.to_string(),
+            contexts: contexts.iter().map(|context| context.to_string()).collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::class_doc::DeclarationKind;
+
+    fn term(ubiquitous: &str, context: &str, qualified_name: &str) -> Ubiquitous {
+        Ubiquitous::new()
+            .set_ubiquitous(ubiquitous.to_string())
+            .set_context(context.to_string())
+            .set_kind(DeclarationKind::Class)
+            .set_class_name(simple_type_name(qualified_name).to_string())
+            .set_qualified_name(qualified_name.to_string())
+    }
+
+    #[test]
+    fn test_context_map() {
+        let list = vec![
+            term("Order", "Sales", "App\\Sales\\Order").set_type_references(vec![
+                "OrderLine".to_string(),
+                "\\App\\Billing\\Invoice".to_string(),
+                "Customer".to_string(),
+                "Money".to_string(),
+                "DateTime".to_string(),
+            ]),
+            term("Order Line", "Sales", "App\\Sales\\OrderLine"),
+            term("Customer", "Sales", "App\\Sales\\Customer"),
+            term("Invoice", "Billing", "App\\Billing\\Invoice")
+                .set_type_references(vec!["Customer".to_string()]),
+            term("Customer", "Billing", "App\\Billing\\Customer"),
+            term("Money", "Shared Kernel", "App\\Shared\\Money"),
+            // コンテキストのない用語は対象外
+            Ubiquitous::new()
+                .set_ubiquitous("Util".to_string())
+                .set_type_references(vec!["Money".to_string()]),
+        ];
+
+        let map = ContextMap::new(&list);
+
+        assert_eq!(map.contexts, vec!["Billing", "Sales", "Shared Kernel"]);
+        assert_eq!(
+            map.adjacency["Sales"],
+            vec![
+                ContextDependency {
+                    context: "Billing".to_string(),
+                    terms: vec!["Invoice".to_string()],
+                },
+                ContextDependency {
+                    context: "Shared Kernel".to_string(),
+                    terms: vec!["Money".to_string()],
+                },
+            ]
+        );
+        // Billing の Customer は同じコンテキスト内の参照
+        assert!(map.adjacency["Billing"].is_empty());
+        assert!(map.adjacency["Shared Kernel"].is_empty());
+        assert_eq!(
+            map.shared_terms,
+            vec![SharedTerm {
+                term: "Customer".to_string(),
+                contexts: vec!["Billing".to_string(), "Sales".to_string()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt::Write;
+use std::{fs, path::Path};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::html::output_assets::create_dir;
+
+use super::{
+    context_map::ContextMap, generate_dot::escape as escape_dot,
+    generate_mermaid::escape as escape_mermaid,
+};
+
+/// コンテキストマップを JSON (隣接リスト)・DOT・Mermaid の3つの形式で出力する
+pub fn generate_context_map(
+    context_map: &ContextMap,
+    output_path: &Path,
+) -> Result<(), UbiDocError> {
+    let outputs = [
+        ("context-map.json", render_context_map_json(context_map)?),
+        ("context-map.dot", render_context_map_dot(context_map)),
+        ("context-map.mmd", render_context_map_mermaid(context_map)),
+    ];
+
+    create_dir(output_path)?;
+    for (file_name, content) in outputs {
+        let path = output_path.join(file_name);
+        fs::write(&path, content).map_err(|source| UbiDocError::Write { path, source })?;
+    }
+    Ok(())
+}
+
+pub fn render_context_map_json(context_map: &ContextMap) -> Result<String, UbiDocError> {
+    Ok(serde_json::to_string_pretty(context_map)?)
+}
+
+/// コンテキストを頂点、依存を辺 (ラベルは使っている用語) とし、
+/// 複数のコンテキストで定義されている用語は色を付けた頂点として定義元のコンテキストとつなぐ
+pub fn render_context_map_dot(context_map: &ContextMap) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "digraph context_map {{").unwrap();
+    writeln!(output, "  rankdir=LR;").unwrap();
+    writeln!(output, "  node [shape=box, style=rounded];").unwrap();
+
+    for (index, context) in context_map.contexts.iter().enumerate() {
+        writeln!(output, "  c{} [label=\"{}\"];", index, escape_dot(context)).unwrap();
+    }
+    for (from, to, terms) in dependencies(context_map) {
+        writeln!(
+            output,
+            "  c{} -> c{} [label=\"{}\"];",
+            from,
+            to,
+            escape_dot(&terms.join(", "))
+        )
+        .unwrap();
+    }
+    for (index, shared_term) in context_map.shared_terms.iter().enumerate() {
+        writeln!(
+            output,
+            "  t{} [label=\"{}\", shape=ellipse, style=filled, fillcolor=\"#fff3c4\"];",
+            index,
+            escape_dot(&shared_term.term)
+        )
+        .unwrap();
+        for context in shared_term.contexts.iter() {
+            if let Some(context_index) = context_index(context_map, context) {
+                writeln!(
+                    output,
+                    "  c{} -> t{} [style=dotted, arrowhead=none];",
+                    context_index, index
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    writeln!(output, "}}").unwrap();
+    output
+}
+
+/// DOT と同じ内容を Mermaid の flowchart で表す
+pub fn render_context_map_mermaid(context_map: &ContextMap) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "flowchart LR").unwrap();
+    for (index, context) in context_map.contexts.iter().enumerate() {
+        writeln!(output, "  c{}[\"{}\"]", index, escape_mermaid(context)).unwrap();
+    }
+    for (from, to, terms) in dependencies(context_map) {
+        writeln!(
+            output,
+            "  c{} -->|\"{}\"| c{}",
+            from,
+            escape_mermaid(&terms.join(", ")),
+            to
+        )
+        .unwrap();
+    }
+    for (index, shared_term) in context_map.shared_terms.iter().enumerate() {
+        writeln!(
+            output,
+            "  t{}([\"{}\"]):::shared",
+            index,
+            escape_mermaid(&shared_term.term)
+        )
+        .unwrap();
+        for context in shared_term.contexts.iter() {
+            if let Some(context_index) = context_index(context_map, context) {
+                writeln!(output, "  c{} -.- t{}", context_index, index).unwrap();
+            }
+        }
+    }
+    if !context_map.shared_terms.is_empty() {
+        writeln!(output, "  classDef shared fill:#fff3c4,stroke:#b08800").unwrap();
+    }
+
+    output
+}
+
+/// 依存関係を (依存元の index, 依存先の index, 用語) の一覧にする
+fn dependencies(context_map: &ContextMap) -> Vec<(usize, usize, &[String])> {
+    context_map
+        .adjacency
+        .iter()
+        .flat_map(|(from, targets)| {
+            targets.iter().filter_map(move |target| {
+                Some((
+                    context_index(context_map, from)?,
+                    context_index(context_map, &target.context)?,
+                    target.terms.as_slice(),
+                ))
+            })
+        })
+        .collect()
+}
+
+fn context_index(context_map: &ContextMap, context: &str) -> Option<usize> {
+    context_map
+        .contexts
+        .iter()
+        .position(|candidate| candidate == context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::graph::context_map::{ContextDependency, SharedTerm};
+    use std::collections::BTreeMap;
+
+    fn context_map() -> ContextMap {
+        ContextMap {
+            contexts: vec!["Billing".to_string(), "Sales".to_string()],
+            adjacency: BTreeMap::from([
+                ("Billing".to_string(), vec![]),
+                (
+                    "Sales".to_string(),
+                    vec![ContextDependency {
+                        context: "Billing".to_string(),
+                        terms: vec!["Invoice".to_string(), "Payment".to_string()],
+                    }],
+                ),
+            ]),
+            shared_terms: vec![SharedTerm {
+                term: "Customer".to_string(),
+                contexts: vec!["Billing".to_string(), "Sales".to_string()],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_context_map_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render_context_map_json(&context_map()).unwrap()).unwrap();
+
+        assert_eq!(json["adjacency"]["Sales"][0]["context"], "Billing");
+        assert_eq!(json["adjacency"]["Sales"][0]["terms"][1], "Payment");
+        assert_eq!(json["adjacency"]["Billing"], serde_json::json!([]));
+        assert_eq!(json["shared_terms"][0]["term"], "Customer");
+    }
+
+    #[test]
+    fn test_render_context_map_dot() {
+        let dot = render_context_map_dot(&context_map());
+
+        assert!(dot.contains("  c0 [label=\"Billing\"];\n"));
+        assert!(dot.contains("  c1 -> c0 [label=\"Invoice, Payment\"];\n"));
+        assert!(dot.contains("  t0 [label=\"Customer\", shape=ellipse"));
+        assert!(dot.contains("  c1 -> t0 [style=dotted, arrowhead=none];\n"));
+    }
+
+    #[test]
+    fn test_render_context_map_mermaid() {
+        let mermaid = render_context_map_mermaid(&context_map());
+
+        assert_eq!(
+            mermaid,
+            "flowchart LR\n  c0[\"Billing\"]\n  c1[\"Sales\"]\n  c1 -->|\"Invoice, Payment\"| c0\n  t0([\"Customer\"]):::shared\n  c0 -.- t0\n  c1 -.- t0\n  classDef shared fill:#fff3c4,stroke:#b08800\n"
+        );
+    }
+}
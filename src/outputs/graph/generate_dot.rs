@@ -0,0 +1,100 @@
+use std::fmt::Write;
+use std::{fs, path::Path};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::html::{output_assets::create_dir, ubiquitous_rows::UbiquitousRows};
+
+use super::term_graph::{EdgeKind, TermGraph};
+
+/// 用語の関連を Graphviz の DOT 形式で出力する
+pub fn generate_dot(
+    ubiquitous_rows: &UbiquitousRows,
+    output_path: &Path,
+) -> Result<(), UbiDocError> {
+    let dot = render_dot(ubiquitous_rows);
+
+    create_dir(output_path)?;
+    let path = output_path.join("ubiquitous.dot");
+    fs::write(&path, dot).map_err(|source| UbiDocError::Write { path, source })
+}
+
+/// コンテキストごとに cluster の subgraph にまとめる
+pub fn render_dot(ubiquitous_rows: &UbiquitousRows) -> String {
+    let graph = TermGraph::new(ubiquitous_rows);
+    let mut output = String::new();
+
+    writeln!(output, "digraph ubiquitous {{").unwrap();
+    writeln!(output, "  rankdir=LR;").unwrap();
+    writeln!(output, "  node [shape=box];").unwrap();
+
+    for (cluster_index, (context, indices)) in graph.clusters().iter().enumerate() {
+        let indent = if context.is_empty() { "  " } else { "    " };
+        if !context.is_empty() {
+            writeln!(output, "  subgraph cluster_{} {{", cluster_index).unwrap();
+            writeln!(output, "    label=\"{}\";", escape(context)).unwrap();
+        }
+        for index in indices {
+            writeln!(
+                output,
+                "{}n{} [label=\"{}\"];",
+                indent,
+                index,
+                escape(graph.nodes[*index].label)
+            )
+            .unwrap();
+        }
+        if !context.is_empty() {
+            writeln!(output, "  }}").unwrap();
+        }
+    }
+
+    for edge in graph.edges.iter() {
+        let style = match edge.kind {
+            EdgeKind::Related => "",
+            EdgeKind::Reference => " [style=dashed]",
+        };
+        writeln!(output, "  n{} -> n{}{};", edge.from, edge.to, style).unwrap();
+    }
+
+    writeln!(output, "}}").unwrap();
+    output
+}
+
+/// DOT のラベル中の \ と " をエスケープする
+pub(super) fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::html::ubiquitous_row::{TermLink, UbiquitousRow};
+
+    #[test]
+    fn test_render_dot() {
+        let mut order = UbiquitousRow::new()
+            .set_anchor("order".to_string())
+            .set_ubiquitous("Order".to_string())
+            .set_context("Sales".to_string());
+        order.references = vec![TermLink {
+            label: "Money".to_string(),
+            anchor: "money".to_string(),
+            resolved: true,
+        }];
+        let money = UbiquitousRow::new()
+            .set_anchor("money".to_string())
+            .set_ubiquitous("\"Money\"".to_string());
+        let rows = UbiquitousRows {
+            rows: vec![order, money],
+        };
+
+        let dot = render_dot(&rows);
+
+        assert!(dot.starts_with("digraph ubiquitous {\n"));
+        assert!(dot.contains(
+            "  subgraph cluster_0 {\n    label=\"Sales\";\n    n0 [label=\"Order\"];\n  }\n"
+        ));
+        assert!(dot.contains("  n1 [label=\"\\\"Money\\\"\"];\n"));
+        assert!(dot.contains("  n0 -> n1 [style=dashed];\n"));
+    }
+}
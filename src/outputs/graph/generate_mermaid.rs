@@ -0,0 +1,101 @@
+use std::fmt::Write;
+use std::{fs, path::Path};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::html::{output_assets::create_dir, ubiquitous_rows::UbiquitousRows};
+
+use super::term_graph::{EdgeKind, TermGraph};
+
+/// 用語の関連を Mermaid 形式で出力する
+pub fn generate_mermaid(
+    ubiquitous_rows: &UbiquitousRows,
+    output_path: &Path,
+) -> Result<(), UbiDocError> {
+    let mermaid = render_mermaid(ubiquitous_rows);
+
+    create_dir(output_path)?;
+    let path = output_path.join("ubiquitous.mmd");
+    fs::write(&path, mermaid).map_err(|source| UbiDocError::Write { path, source })
+}
+
+/// コンテキストごとに subgraph にまとめた flowchart (HTML にも埋め込む)
+pub fn render_mermaid(ubiquitous_rows: &UbiquitousRows) -> String {
+    let graph = TermGraph::new(ubiquitous_rows);
+    let mut output = String::new();
+
+    writeln!(output, "flowchart LR").unwrap();
+
+    for (cluster_index, (context, indices)) in graph.clusters().iter().enumerate() {
+        let indent = if context.is_empty() { "  " } else { "    " };
+        if !context.is_empty() {
+            writeln!(
+                output,
+                "  subgraph c{}[\"{}\"]",
+                cluster_index,
+                escape(context)
+            )
+            .unwrap();
+        }
+        for index in indices {
+            writeln!(
+                output,
+                "{}n{}[\"{}\"]",
+                indent,
+                index,
+                escape(graph.nodes[*index].label)
+            )
+            .unwrap();
+        }
+        if !context.is_empty() {
+            writeln!(output, "  end").unwrap();
+        }
+    }
+
+    for edge in graph.edges.iter() {
+        let arrow = match edge.kind {
+            EdgeKind::Related => "-->",
+            EdgeKind::Reference => "-.->",
+        };
+        writeln!(output, "  n{} {} n{}", edge.from, arrow, edge.to).unwrap();
+    }
+
+    output
+}
+
+/// Mermaid のラベル中の " は #quot; で表す
+pub(super) fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::html::ubiquitous_row::{TermLink, UbiquitousRow};
+
+    #[test]
+    fn test_render_mermaid() {
+        let mut order = UbiquitousRow::new()
+            .set_anchor("order".to_string())
+            .set_ubiquitous("Order".to_string())
+            .set_context("Sales".to_string());
+        order.related = vec![TermLink {
+            label: "Invoice".to_string(),
+            anchor: "invoice".to_string(),
+            resolved: true,
+        }];
+        let invoice = UbiquitousRow::new()
+            .set_anchor("invoice".to_string())
+            .set_ubiquitous("\"Invoice\"".to_string())
+            .set_context("Billing".to_string());
+        let rows = UbiquitousRows {
+            rows: vec![order, invoice],
+        };
+
+        let mermaid = render_mermaid(&rows);
+
+        assert_eq!(
+            mermaid,
+            "flowchart LR\n  subgraph c0[\"Sales\"]\n    n0[\"Order\"]\n  end\n  subgraph c1[\"Billing\"]\n    n1[\"#quot;Invoice#quot;\"]\n  end\n  n0 --> n1\n"
+        );
+    }
+}
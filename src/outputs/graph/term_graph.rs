@@ -0,0 +1,151 @@
+use crate::outputs::html::{ubiquitous_row::UbiquitousRow, ubiquitous_rows::UbiquitousRows};
+
+/// 用語を頂点、用語間の参照を辺とするグラフ
+pub struct TermGraph<'a> {
+    pub nodes: Vec<GraphNode<'a>>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// グラフの頂点 (用語1件分)
+pub struct GraphNode<'a> {
+    pub label: &'a str,
+    pub context: &'a str,
+}
+
+/// 辺の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// @see / @related
+    Related,
+    /// 説明文中の {@link Term} / [[Term]]
+    Reference,
+}
+
+/// グラフの辺 (from, to は nodes の index)
+#[derive(Debug, PartialEq)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+impl<'a> TermGraph<'a> {
+    /// enum の列挙子も含めたすべての行を頂点にし、参照先が見つかった参照だけを辺にする
+    pub fn new(rows: &'a UbiquitousRows) -> Self {
+        let all_rows: Vec<&UbiquitousRow> = rows
+            .rows
+            .iter()
+            .flat_map(|row| std::iter::once(row).chain(row.children.iter()))
+            .collect();
+        let index_of = |anchor: &str| {
+            all_rows
+                .iter()
+                .position(|row| !anchor.is_empty() && row.anchor == anchor)
+        };
+
+        let mut edges: Vec<GraphEdge> = Vec::new();
+        for (from, row) in all_rows.iter().enumerate() {
+            let links = row
+                .related
+                .iter()
+                .map(|link| (link, EdgeKind::Related))
+                .chain(
+                    row.references
+                        .iter()
+                        .map(|link| (link, EdgeKind::Reference)),
+                );
+            for (link, kind) in links {
+                let Some(to) = index_of(&link.anchor) else {
+                    continue;
+                };
+                // 自分自身への参照と、同じ2つの用語の間の重複した辺は除く
+                if to == from || edges.iter().any(|edge| edge.from == from && edge.to == to) {
+                    continue;
+                }
+                edges.push(GraphEdge { from, to, kind });
+            }
+        }
+
+        let nodes = all_rows
+            .iter()
+            .map(|row| GraphNode {
+                label: &row.ubiquitous,
+                context: &row.context,
+            })
+            .collect();
+        TermGraph { nodes, edges }
+    }
+
+    /// コンテキストごとの頂点の index (コンテキストの出現順。コンテキストのない頂点は空文字のまとまり)
+    pub fn clusters(&self) -> Vec<(&'a str, Vec<usize>)> {
+        let mut clusters: Vec<(&str, Vec<usize>)> = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            match clusters
+                .iter_mut()
+                .find(|(context, _)| *context == node.context)
+            {
+                Some((_, indices)) => indices.push(index),
+                None => clusters.push((node.context, vec![index])),
+            }
+        }
+        clusters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::html::ubiquitous_row::TermLink;
+
+    fn link(anchor: &str) -> TermLink {
+        TermLink {
+            label: anchor.to_string(),
+            anchor: anchor.to_string(),
+            resolved: true,
+        }
+    }
+
+    #[test]
+    fn test_term_graph() {
+        let mut order = UbiquitousRow::new()
+            .set_anchor("order".to_string())
+            .set_ubiquitous("Order".to_string())
+            .set_context("Sales".to_string());
+        order.related = vec![link("line"), link("unknown"), link("order")];
+        order.references = vec![link("line"), link("invoice")];
+        let line = UbiquitousRow::new()
+            .set_anchor("line".to_string())
+            .set_ubiquitous("Order Line".to_string())
+            .set_context("Sales".to_string());
+        let invoice = UbiquitousRow::new()
+            .set_anchor("invoice".to_string())
+            .set_ubiquitous("Invoice".to_string())
+            .set_context("Billing".to_string());
+        let rows = UbiquitousRows {
+            rows: vec![order, line, invoice],
+        };
+
+        let graph = TermGraph::new(&rows);
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(
+            graph.edges,
+            vec![
+                GraphEdge {
+                    from: 0,
+                    to: 1,
+                    kind: EdgeKind::Related
+                },
+                GraphEdge {
+                    from: 0,
+                    to: 2,
+                    kind: EdgeKind::Reference
+                },
+            ]
+        );
+        assert_eq!(
+            graph.clusters(),
+            vec![("Sales", vec![0, 1]), ("Billing", vec![2])]
+        );
+    }
+}
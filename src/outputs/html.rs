@@ -3,3 +3,4 @@ pub mod output_assets;
 pub mod render_html;
 pub mod ubiquitous_row;
 pub mod ubiquitous_rows;
+pub mod ui_messages;
@@ -1,10 +1,35 @@
 use super::{
-    output_assets::output_assets, render_html::render_html, ubiquitous_rows::UbiquitousRows,
+    output_assets::{output_assets, output_vendored_asset},
+    render_html::render_html,
+    ubiquitous_rows::UbiquitousRows,
+    ui_messages::UiMessages,
 };
+use crate::config::ubi_doc_config::is_url;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
 use std::path::Path;
 
-pub fn generate_html(mut ubiquitous_rows: UbiquitousRows, output_path: &Path) {
-    ubiquitous_rows.sort();
-    let rendered_html: String = render_html(ubiquitous_rows);
-    output_assets(&rendered_html, output_path);
+/// extra_fields: 設定で追加したタグ (設定に書かれた順に列を追加する)
+/// mermaid_script: 用語の関連図を描画する Mermaid のスクリプト (None の場合は関連図を埋め込まない)
+pub fn generate_html(
+    ubiquitous_rows: &UbiquitousRows,
+    output_path: &Path,
+    template_dir: Option<&Path>,
+    ui_messages: &UiMessages,
+    extra_fields: &[String],
+    mermaid_script: Option<&str>,
+) -> Result<(), UbiDocError> {
+    // ファイルは出力ディレクトリにコピーし、HTML からは相対パスで読み込む
+    let mermaid_src = match mermaid_script {
+        Some(script) if is_url(script) => Some(script.to_string()),
+        Some(script) => Some(output_vendored_asset(Path::new(script), output_path)?),
+        None => None,
+    };
+    let rendered_html: String = render_html(
+        ubiquitous_rows,
+        template_dir,
+        ui_messages,
+        extra_fields,
+        mermaid_src.as_deref(),
+    )?;
+    output_assets(&rendered_html, output_path, template_dir)
 }
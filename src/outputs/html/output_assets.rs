@@ -1,37 +1,66 @@
 use std::{
-    fs::{self, File},
-    io::Write,
+    fs,
     path::{Path, PathBuf},
 };
 
-pub fn output_assets(rendered_html: &String, output_path: &Path) {
-    fs::create_dir_all(output_path).unwrap();
+use crate::diagnostics::ubi_doc_error::UbiDocError;
 
-    let mut file = File::create(output_path.join("index.html")).unwrap();
-    file.write_all(rendered_html.as_bytes()).unwrap();
+use super::render_html::builtin_templates_dir;
 
-    // コンパイル時に設定されるリポジトリのルートパスを取得
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let templates_dir = Path::new(manifest_dir).join("src/outputs/html/templates");
+/// HTML と script.js / style.css を出力する
+/// template_dir に script.js / style.css があればそちらを優先してコピーする
+pub fn output_assets(
+    rendered_html: &String,
+    output_path: &Path,
+    template_dir: Option<&Path>,
+) -> Result<(), UbiDocError> {
+    create_dir(output_path)?;
+
+    let index_path = output_path.join("index.html");
+    fs::write(&index_path, rendered_html.as_bytes()).map_err(|source| UbiDocError::Write {
+        path: index_path,
+        source,
+    })?;
 
     // script.js と style.css をコピー
-    copy_file(
-        &templates_dir.join("script.js"),
-        &output_path.join("script.js"),
-    );
-    copy_file(
-        &templates_dir.join("style.css"),
-        &output_path.join("style.css"),
-    );
+    for asset in ["script.js", "style.css"] {
+        let custom = template_dir
+            .map(|template_dir| template_dir.join(asset))
+            .filter(|path| path.is_file());
+        let src = custom.unwrap_or_else(|| builtin_templates_dir().join(asset));
+        copy_file(&src, &output_path.join(asset))?;
+    }
+
+    Ok(())
 }
 
-fn copy_file(src: &Path, dst: &PathBuf) {
+/// ローカルのスクリプトなどを出力ディレクトリにコピーし、HTML から読み込むパス (./ファイル名) を返す
+pub fn output_vendored_asset(src: &Path, output_path: &Path) -> Result<String, UbiDocError> {
+    let file_name = src
+        .file_name()
+        .filter(|_| src.is_file())
+        .ok_or_else(|| UbiDocError::Config(format!("Asset file not found: {:?}", src)))?;
+    copy_file(src, &output_path.join(file_name))?;
+    Ok(format!("./{}", file_name.to_string_lossy()))
+}
+
+fn copy_file(src: &Path, dst: &PathBuf) -> Result<(), UbiDocError> {
     if let Some(parent) = dst.parent() {
-        fs::create_dir_all(parent).unwrap();
+        create_dir(parent)?;
     }
-    fs::copy(src, dst).unwrap_or_else(|e| {
-        panic!("Failed to copy from {:?} to {:?}: {}", src, dst, e);
-    });
+    fs::copy(src, dst).map_err(|source| UbiDocError::Write {
+        path: dst.clone(),
+        source,
+    })?;
+    Ok(())
+}
+
+/// 出力ディレクトリを作成する
+pub fn create_dir(path: &Path) -> Result<(), UbiDocError> {
+    fs::create_dir_all(path).map_err(|source| UbiDocError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
 }
 
 // テストモジュール
@@ -47,7 +76,7 @@ mod tests {
         let output_path = tmp_dir.path().to_path_buf();
 
         let dummy_html = "<html><body>Test Content</body></html>".to_string();
-        output_assets(&dummy_html, &output_path);
+        output_assets(&dummy_html, &output_path, None).unwrap();
 
         let html_file = output_path.join("index.html");
         assert!(html_file.exists(), "ubiquitous.html should exist");
@@ -59,4 +88,55 @@ mod tests {
         assert!(script_path.exists(), "script.js should be copied");
         assert!(style_path.exists(), "style.css should be copied");
     }
+
+    #[test]
+    fn test_output_assets_with_template_dir() {
+        let template_dir = tempdir().unwrap();
+        fs::write(template_dir.path().join("style.css"), "body {}").unwrap();
+
+        let tmp_dir = tempdir().unwrap();
+        let output_path = tmp_dir.path().to_path_buf();
+        output_assets(
+            &"<html></html>".to_string(),
+            &output_path,
+            Some(template_dir.path()),
+        )
+        .unwrap();
+
+        // style.css はテンプレートディレクトリのもの、script.js は組み込みのもの
+        let style = fs::read_to_string(output_path.join("style.css")).unwrap();
+        assert_eq!(style, "body {}");
+        assert!(output_path.join("script.js").exists());
+    }
+
+    #[test]
+    fn test_output_vendored_asset() {
+        let src_dir = tempdir().unwrap();
+        let script = src_dir.path().join("mermaid.esm.min.mjs");
+        fs::write(&script, "export default {};").unwrap();
+        let tmp_dir = tempdir().unwrap();
+        let output_path = tmp_dir.path().join("docs");
+
+        let src = output_vendored_asset(&script, &output_path).unwrap();
+
+        assert_eq!(src, "./mermaid.esm.min.mjs");
+        let copied = fs::read_to_string(output_path.join("mermaid.esm.min.mjs")).unwrap();
+        assert_eq!(copied, "export default {};");
+        assert!(matches!(
+            output_vendored_asset(&src_dir.path().join("missing.mjs"), &output_path),
+            Err(UbiDocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_output_assets_unwritable_output() {
+        let tmp_dir = tempdir().unwrap();
+        // 出力先にファイルがあるとディレクトリを作成できない
+        let output_path = tmp_dir.path().join("docs");
+        fs::write(&output_path, "").unwrap();
+
+        let result = output_assets(&"<html></html>".to_string(), &output_path, None);
+
+        assert!(matches!(result, Err(UbiDocError::Write { .. })));
+    }
 }
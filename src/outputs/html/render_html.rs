@@ -1,27 +1,63 @@
-use super::ubiquitous_rows::UbiquitousRows;
-use std::path::PathBuf;
+use super::{ubiquitous_rows::UbiquitousRows, ui_messages::UiMessages};
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::graph::{generate_mermaid::render_mermaid, term_graph::TermGraph};
+use std::path::{Path, PathBuf};
 use tera::{Context, Tera};
 
-pub fn render_html(rows: UbiquitousRows) -> String {
-    // コンパイル時に設定されたリポジトリのルートパスを取得
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
-    let mut templates_path = PathBuf::from(manifest_dir);
-    templates_path.push("src/outputs/html/templates/*.html");
+/// template_dir が指定された場合はそのディレクトリの ubiquitous.html を使う
+/// テンプレートには全行 (items) とコンテキストごとのまとまり (groups)、
+/// 翻訳があるロケール (locales)、追加のタグの列 (extra_fields)、用語の関連図 (mermaid, mermaid_src)、
+/// 画面の文言 (ui_lang, messages) を渡す
+/// extra_fields: 設定で追加したタグ (設定に書かれた順に列を追加する)
+/// mermaid_src: 関連図を描画する Mermaid のスクリプトの URL (None の場合は関連図を埋め込まない)
+pub fn render_html(
+    rows: &UbiquitousRows,
+    template_dir: Option<&Path>,
+    ui_messages: &UiMessages,
+    extra_fields: &[String],
+    mermaid_src: Option<&str>,
+) -> Result<String, UbiDocError> {
+    let mut templates_path = match template_dir {
+        Some(template_dir) => template_dir.to_path_buf(),
+        None => builtin_templates_dir(),
+    };
+    templates_path.push("*.html");
 
-    let tera = Tera::new(templates_path.to_str().unwrap())
-        .expect("Failed to init Tera with templates/*.html");
+    let tera = Tera::new(&templates_path.to_string_lossy())?;
 
     let mut context = Context::new();
     context.insert("items", &rows.rows);
+    context.insert("groups", &rows.group_by_context());
+    context.insert("locales", &rows.locales());
+    context.insert("extra_fields", extra_fields);
+    // 用語の関連 (辺) がなければ関連図は出さない
+    let mermaid = match mermaid_src {
+        Some(_) if !TermGraph::new(rows).edges.is_empty() => render_mermaid(rows),
+        _ => String::new(),
+    };
+    context.insert("mermaid", &mermaid);
+    context.insert("mermaid_src", mermaid_src.unwrap_or_default());
+    context.insert("ui_lang", &ui_messages.lang);
+    context.insert("messages", &ui_messages.messages);
 
-    tera.render("ubiquitous.html", &context)
-        .expect("Failed to render template")
+    Ok(tera.render("ubiquitous.html", &context)?)
+}
+
+/// 組み込みテンプレートのディレクトリ
+pub fn builtin_templates_dir() -> PathBuf {
+    // コンパイル時に設定されたリポジトリのルートパスを取得
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    Path::new(manifest_dir).join("src/outputs/html/templates")
 }
 
 #[cfg(test)]
 mod tests {
     use super::{render_html, UbiquitousRows};
-    use crate::outputs::html::ubiquitous_row::UbiquitousRow;
+    use crate::config::ubi_doc_config::LinkConfig;
+    use crate::outputs::html::ubiquitous_row::{TermLink, UbiquitousRow};
+    use crate::outputs::html::ui_messages::UiMessages;
+    use crate::parser::{stereotype::Stereotype, term_index::TermIndex, translation::Translation};
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_render_html() {
@@ -31,9 +67,15 @@ mod tests {
             .set_ubiquitous("ubiquitous1".to_string())
             .set_context("Context1".to_string())
             .set_description("some description".to_string())
-            .set_github_url(
-                "owner/repo".to_string(),
-                "main".to_string(),
+            .set_stereotype(Some(Stereotype::Entity))
+            .set_teams(vec!["@acme/sales".to_string()])
+            .set_rules(vec!["must <not> be empty".to_string()])
+            .set_source_link(
+                &LinkConfig {
+                    repo: Some("owner/repo".to_string()),
+                    branch: Some("main".to_string()),
+                    ..LinkConfig::default()
+                },
                 "src/file.rs".to_string(),
                 10,
             );
@@ -43,9 +85,12 @@ mod tests {
             .set_ubiquitous("ubiquitous2".to_string())
             .set_context("Context2".to_string())
             .set_description("another description".to_string())
-            .set_github_url(
-                "owner/repo".to_string(),
-                "dev".to_string(),
+            .set_source_link(
+                &LinkConfig {
+                    repo: Some("owner/repo".to_string()),
+                    branch: Some("dev".to_string()),
+                    ..LinkConfig::default()
+                },
                 "src/another.rs".to_string(),
                 20,
             );
@@ -55,10 +100,10 @@ mod tests {
         };
 
         // render_htmlを呼び出し、返ってきたHTML文字列を検証
-        let output = render_html(rows);
+        let output = render_html(&rows, None, &UiMessages::default(), &[], None).unwrap();
 
         // 基本的なタグが含まれているか
-        assert!(output.contains("<html>"));
+        assert!(output.contains(r#"<html lang="en">"#));
         assert!(output.contains("<h1>Ubiquitous Language</h1>"));
         assert!(output.contains("<table"));
         assert!(output.contains("</table>"));
@@ -80,5 +125,184 @@ mod tests {
         assert!(
             output.contains(r#"href="https://github.com/owner/repo/blob/dev/src/another.rs#L20""#)
         );
+
+        // コンテキストごとに見出しが出力される
+        assert_eq!(output.matches(r#"<tr class="context-header">"#).count(), 2);
+
+        // 業務ルールは箇条書きで、エスケープして出力される
+        assert!(output.contains("<li>must &lt;not&gt; be empty</li>"));
+
+        // DDD の種類はバッジと絞り込み用の属性で出力される
+        assert!(output.contains(r#"data-stereotype="entity""#));
+        assert!(output.contains(
+            r#"class="stereotype-badge stereotype-entity" data-label="Entity">Entity</span>"#
+        ));
+
+        // 担当チームは列と絞り込み用の <select> で出力される
+        // (Tera の自動エスケープで "/" は &#x2F; になる)
+        assert!(output.contains(r#"<span class="team">@acme&#x2F;sales</span>"#));
+        assert!(output.contains(r#"<select id="team-select">"#));
+
+        // Mermaid のスクリプトを指定しなければ関連図は埋め込まない
+        assert!(!output.contains(r#"<pre class="mermaid">"#));
+        assert!(!output.contains("import mermaid"));
+
+        // 翻訳がなければ言語の切り替えは表示しない
+        assert!(!output.contains(r#"id="language-select""#));
+    }
+
+    #[test]
+    fn test_render_html_term_graph() {
+        let mut order = UbiquitousRow::new()
+            .set_anchor("order".to_string())
+            .set_ubiquitous("Order".to_string());
+        order.related = vec![TermLink {
+            label: "Invoice".to_string(),
+            anchor: "invoice".to_string(),
+            resolved: true,
+        }];
+        let invoice = UbiquitousRow::new()
+            .set_anchor("invoice".to_string())
+            .set_ubiquitous("Invoice".to_string());
+        let rows = UbiquitousRows {
+            rows: vec![order, invoice],
+        };
+
+        let output = render_html(
+            &rows,
+            None,
+            &UiMessages::default(),
+            &[],
+            Some("./mermaid.esm.min.mjs"),
+        )
+        .unwrap();
+
+        assert!(output.contains(r#"<pre class="mermaid">flowchart LR"#));
+        assert!(output.contains(r#"import mermaid from "./mermaid.esm.min.mjs";"#));
+
+        // 関連のない用語だけなら、スクリプトを指定しても関連図は埋め込まない
+        let rows = UbiquitousRows {
+            rows: vec![UbiquitousRow::new().set_ubiquitous("Money".to_string())],
+        };
+        let output = render_html(
+            &rows,
+            None,
+            &UiMessages::default(),
+            &[],
+            Some("./mermaid.esm.min.mjs"),
+        )
+        .unwrap();
+        assert!(!output.contains("import mermaid"));
+    }
+
+    #[test]
+    fn test_render_html_ui_lang() {
+        let row = UbiquitousRow::new()
+            .set_ubiquitous("Money".to_string())
+            .set_stereotype(Some(Stereotype::ValueObject));
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let output =
+            render_html(&rows, None, &UiMessages::builtin("ja").unwrap(), &[], None).unwrap();
+
+        assert!(output.contains(r#"<html lang="ja">"#));
+        assert!(output.contains("<h1>ユビキタス言語</h1>"));
+        assert!(output.contains("<th>コンテキスト</th>"));
+        assert!(output.contains("(コンテキストなし)"));
+        assert!(output.contains(r#"data-label="値オブジェクト">値オブジェクト</span>"#));
+        assert!(!output.contains("Class Name"));
+    }
+
+    #[test]
+    fn test_render_html_extra_fields() {
+        let row1 = UbiquitousRow::new()
+            .set_ubiquitous("Order".to_string())
+            .set_extra_fields(BTreeMap::from([(
+                "jira".to_string(),
+                "SALES-12".to_string(),
+            )]));
+        let row2 = UbiquitousRow::new().set_ubiquitous("Invoice".to_string());
+        let rows = UbiquitousRows {
+            rows: vec![row1, row2],
+        };
+        let extra_fields = ["status".to_string(), "jira".to_string()];
+
+        let output = render_html(&rows, None, &UiMessages::default(), &extra_fields, None).unwrap();
+
+        // 設定に書かれた順に、値がない列も含めて追加する
+        let status = output
+            .find(r#"<th class="extra-field">status</th>"#)
+            .unwrap();
+        let jira = output.find(r#"<th class="extra-field">jira</th>"#).unwrap();
+        assert!(status < jira);
+        assert!(output.contains(r#"<th colspan="8">"#));
+        assert!(output.contains(r#"<td class="extra-field">SALES-12</td>"#));
+        // 値がない行は空欄にする
+        assert!(output.contains(r#"<td class="extra-field"></td>"#));
+    }
+
+    #[test]
+    fn test_render_html_translations() {
+        let translations = BTreeMap::from([(
+            "ja".to_string(),
+            Translation {
+                ubiquitous: Some("注文".to_string()),
+                description: None,
+            },
+        )]);
+        let row = UbiquitousRow::new()
+            .set_ubiquitous("Order".to_string())
+            .set_translations(&TermIndex::new(&[]), &translations);
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let output = render_html(&rows, None, &UiMessages::default(), &[], None).unwrap();
+
+        assert!(output.contains(r#"<option value="ja">ja</option>"#));
+        assert!(output.contains(
+            r#"<span class="term" data-l10n="term" data-locale="ja" lang="ja" hidden>注文</span>"#
+        ));
+        // 説明の翻訳がない場合は既定の説明だけを出力する
+        assert!(!output.contains(r#"data-l10n="description" data-locale="ja""#));
+    }
+
+    #[test]
+    fn test_render_html_with_template_dir() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp_dir.path().join("ubiquitous.html"),
+            "{% for item in items %}<p>{{ item.ubiquitous }}</p>{% endfor %}",
+        )
+        .unwrap();
+
+        let rows = UbiquitousRows {
+            rows: vec![UbiquitousRow::new().set_ubiquitous("Order".to_string())],
+        };
+
+        let output = render_html(
+            &rows,
+            Some(tmp_dir.path()),
+            &UiMessages::default(),
+            &[],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(output, "<p>Order</p>");
+    }
+
+    #[test]
+    fn test_render_html_missing_template() {
+        // ubiquitous.html がないテンプレートディレクトリはエラーになる
+        let tmp_dir = tempfile::tempdir().unwrap();
+
+        let result = render_html(
+            &UbiquitousRows::new(),
+            Some(tmp_dir.path()),
+            &UiMessages::default(),
+            &[],
+            None,
+        );
+
+        assert!(result.is_err());
     }
 }
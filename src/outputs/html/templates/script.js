@@ -1,13 +1,17 @@
 document.addEventListener("DOMContentLoaded", () => {
-    const tableBody = document.querySelector("#ubitable tbody");
+    const table = document.getElementById("ubitable");
     const keywordInput = document.getElementById("keyword-input");
     const contextSelect = document.getElementById("context-select");
+    const stereotypeSelect = document.getElementById("stereotype-select");
+    const teamSelect = document.getElementById("team-select");
+    const languageSelect = document.getElementById("language-select");
   
-    if (!tableBody || !contextSelect) return;
+    if (!table || !contextSelect) return;
   
     // --- 1. ユニークなContext値を抽出して<select>に追加 ---
     const contextSet = new Set();
-    const rows = tableBody.querySelectorAll("tr");
+    const rows = table.querySelectorAll("tr.term-row");
+    const groups = table.querySelectorAll("tbody.context-group");
     
     rows.forEach(row => {
       const cells = row.querySelectorAll("td");
@@ -31,10 +35,43 @@ document.addEventListener("DOMContentLoaded", () => {
       contextSelect.appendChild(option);
     });
   
+    // DDD の種類 (stereotype) も同様に <select> に追加
+    const stereotypeMap = new Map();
+    table.querySelectorAll(".stereotype-badge").forEach(badge => {
+      const row = badge.closest("tr.term-row");
+      if (row && row.dataset.stereotype) {
+        stereotypeMap.set(row.dataset.stereotype, badge.dataset.label || row.dataset.stereotype);
+      }
+    });
+    if (stereotypeSelect) {
+      Array.from(stereotypeMap.keys()).sort().forEach(value => {
+        const option = document.createElement("option");
+        option.value = value;
+        option.textContent = stereotypeMap.get(value);
+        stereotypeSelect.appendChild(option);
+      });
+    }
+
+    // 担当チームも同様に <select> に追加
+    const teamSet = new Set();
+    table.querySelectorAll("tr.term-row .team").forEach(team => {
+      teamSet.add(team.innerText.trim());
+    });
+    if (teamSelect) {
+      Array.from(teamSet).sort().forEach(value => {
+        const option = document.createElement("option");
+        option.value = value;
+        option.textContent = value;
+        teamSelect.appendChild(option);
+      });
+    }
+
     // --- 2. フィルタ処理 ---
     function filterTable() {
       const keyword = (keywordInput?.value || "").toLowerCase().trim();
       const selectedContext = contextSelect?.value || "";
+      const selectedStereotype = stereotypeSelect?.value || "";
+      const selectedTeam = teamSelect?.value || "";
     
       Array.from(rows).forEach(row => {
         const rowText = row.innerText.toLowerCase();
@@ -43,19 +80,61 @@ document.addEventListener("DOMContentLoaded", () => {
     
         const matchKeyword = rowText.includes(keyword);
         const matchContext = !selectedContext || (contextText === selectedContext);
+        const matchStereotype = !selectedStereotype || (row.dataset.stereotype === selectedStereotype);
+        const matchTeam = !selectedTeam
+          || Array.from(row.querySelectorAll(".team")).some(team => team.innerText.trim() === selectedTeam);
     
-        if (matchKeyword && matchContext) {
+        if (matchKeyword && matchContext && matchStereotype && matchTeam) {
           row.classList.remove("hide");
         } else {
           row.classList.add("hide");
         }
       });
+
+      // 表示する行がないコンテキストは見出しごと隠す
+      groups.forEach(group => {
+        const hasVisibleRow = group.querySelector("tr.term-row:not(.hide)") !== null;
+        group.classList.toggle("hide", !hasVisibleRow);
+      });
     }
     
+    // --- 言語の切り替え ---
+    // 行ごとに data-l10n が同じ要素のうち、選んだ言語のもの (なければ既定の言語のもの) だけを表示する
+    function switchLanguage() {
+      const locale = languageSelect?.value || "";
+      rows.forEach(row => {
+        const groupsByKey = new Map();
+        row.querySelectorAll("[data-l10n]").forEach(element => {
+          const key = element.dataset.l10n;
+          if (!groupsByKey.has(key)) groupsByKey.set(key, []);
+          groupsByKey.get(key).push(element);
+        });
+        groupsByKey.forEach(elements => {
+          const target = elements.find(element => element.dataset.locale === locale)
+            || elements.find(element => element.dataset.locale === "");
+          elements.forEach(element => {
+            element.hidden = element !== target;
+          });
+        });
+      });
+    }
+
     // --- 3. イベント登録 ---
     if (keywordInput) {
       keywordInput.addEventListener("input", filterTable);
     }
     contextSelect.addEventListener("change", filterTable);
+    if (stereotypeSelect) {
+      stereotypeSelect.addEventListener("change", filterTable);
+    }
+    if (teamSelect) {
+      teamSelect.addEventListener("change", filterTable);
+    }
+    if (languageSelect) {
+      languageSelect.addEventListener("change", () => {
+        switchLanguage();
+        filterTable();
+      });
+    }
   });
   
\ No newline at end of file
@@ -1,26 +1,124 @@
+use std::collections::BTreeMap;
+
 use serde::Serialize;
 
+use crate::config::ubi_doc_config::LinkConfig;
+use crate::diagnostics::diagnostic::Severity;
+use crate::lint::lint_message::LintMessage;
+use crate::parser::{
+    stereotype::Stereotype,
+    term_index::TermIndex,
+    term_reference::{split_inline_references, DescriptionSegment, TermReference},
+    translation::Translation,
+};
+
+/// ほかの用語へのリンク
+#[derive(Serialize, Clone)]
+pub struct TermLink {
+    pub label: String,
+    /// リンク先の行の id (参照先が見つからないか、出力しない用語であれば空)
+    pub anchor: String,
+    /// 参照先の用語が見つかったかどうか (--owner で出力しない用語も含む)
+    pub resolved: bool,
+}
+
+/// ロケールごとの用語名・説明 (翻訳がない項目は空)
+#[derive(Serialize)]
+pub struct LocalizedTerm {
+    pub ubiquitous: String,
+    pub description: String,
+    /// 参照をリンクにした HTML の説明文
+    pub description_html: String,
+}
+
 #[derive(Serialize)]
 pub struct UbiquitousRow {
+    /// HTML でこの行を参照するための id
+    pub anchor: String,
     pub class_name: String,
+    /// 名前空間・外側のクラスを含めた完全修飾名
+    pub qualified_name: String,
+    /// class / interface / enum など
+    pub kind: String,
+    /// メソッド・列挙子などを宣言しているクラスの完全修飾名
+    pub parent: String,
+    /// クラスが属するパッケージ・名前空間・モジュール
+    pub namespace: String,
     pub ubiquitous: String,
     pub context: String,
     pub description: String,
+    /// 参照をリンクにした HTML の説明文
+    pub description_html: String,
+    /// 関連する用語 (@see / @related)
+    pub related: Vec<TermLink>,
+    /// 説明文中で参照している用語 ({@link Term} / [[Term]])
+    pub references: Vec<TermLink>,
+    /// 別名・同義語
+    pub aliases: Vec<String>,
+    /// 使用をやめた用語かどうか
+    pub deprecated: bool,
+    /// 使用をやめた用語の置き換え先
+    pub replaced_by: String,
+    /// aggregate / entity / value-object など (HTML の絞り込みに使う)
+    pub stereotype: String,
+    /// stereotype の表示名
+    pub stereotype_label: String,
+    /// 所属する集約ルートの用語
+    pub aggregate_root: Option<TermLink>,
+    /// 集約ルートに所属する用語
+    pub aggregate_members: Vec<TermLink>,
+    /// 不変条件・業務ルール
+    pub rules: Vec<String>,
+    /// 使用例
+    pub examples: Vec<String>,
+    /// ロケール (ja など) ごとの翻訳
+    pub translations: BTreeMap<String, LocalizedTerm>,
+    /// 用語を担当するチーム
+    pub teams: Vec<String>,
+    /// 設定で追加したタグの名前と値
+    pub extra_fields: BTreeMap<String, String>,
     pub file_path: String,
     pub line_number: usize,
     pub github_url: String,
+    pub lint_level: String,
+    pub lint_messages: Vec<String>,
+    /// enum の用語の下にまとめる列挙子の用語
+    pub children: Vec<UbiquitousRow>,
 }
 
 impl UbiquitousRow {
     pub fn new() -> Self {
         UbiquitousRow {
+            anchor: "".to_string(),
             class_name: "".to_string(),
+            qualified_name: "".to_string(),
+            kind: "".to_string(),
+            parent: "".to_string(),
+            namespace: "".to_string(),
             ubiquitous: "".to_string(),
             context: "".to_string(),
             description: "".to_string(),
+            description_html: "".to_string(),
+            related: Vec::new(),
+            references: Vec::new(),
+            aliases: Vec::new(),
+            deprecated: false,
+            replaced_by: "".to_string(),
+            stereotype: "".to_string(),
+            stereotype_label: "".to_string(),
+            aggregate_root: None,
+            aggregate_members: Vec::new(),
+            rules: Vec::new(),
+            examples: Vec::new(),
+            translations: BTreeMap::new(),
+            teams: Vec::new(),
+            extra_fields: BTreeMap::new(),
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            lint_level: "".to_string(),
+            lint_messages: Vec::new(),
+            children: Vec::new(),
         }
     }
 
@@ -29,6 +127,26 @@ impl UbiquitousRow {
         self
     }
 
+    pub fn set_qualified_name(mut self, qualified_name: String) -> Self {
+        self.qualified_name = qualified_name;
+        self
+    }
+
+    pub fn set_kind(mut self, kind: String) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn set_parent(mut self, parent: String) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    pub fn set_namespace(mut self, namespace: String) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
     pub fn set_ubiquitous(mut self, ubiquitous: String) -> Self {
         self.ubiquitous = ubiquitous;
         self
@@ -39,15 +157,104 @@ impl UbiquitousRow {
         self
     }
 
+    /// 説明文を設定する (参照をリンクにするには、続けて set_references を呼び出す)
     pub fn set_description(mut self, description: String) -> Self {
+        self.description_html = escape_html(&description);
         self.description = description;
         self
     }
 
-    pub fn set_github_url(
+    pub fn set_anchor(mut self, anchor: String) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// 関連する用語と説明文中の参照を、用語集の行へのリンクにする
+    /// (set_description / set_context の後に呼び出す)
+    pub fn set_references(mut self, term_index: &TermIndex, related: &[String]) -> Self {
+        let (description_html, references) =
+            link_description(&self.description, term_index, &self.context);
+        self.description_html = description_html;
+        self.references = references;
+        self.related = related
+            .iter()
+            .map(|reference| term_link(reference, term_index, &self.context))
+            .collect();
+        self
+    }
+
+    /// 翻訳を設定する。説明文中の参照は set_references と同じくリンクにする (set_context の後に呼び出す)
+    pub fn set_translations(
         mut self,
-        repo: String,
-        branch: String,
+        term_index: &TermIndex,
+        translations: &BTreeMap<String, Translation>,
+    ) -> Self {
+        self.translations = translations
+            .iter()
+            .map(|(locale, translation)| {
+                let description = translation.description.clone().unwrap_or_default();
+                let (description_html, _) =
+                    link_description(&description, term_index, &self.context);
+                let localized = LocalizedTerm {
+                    ubiquitous: translation.ubiquitous.clone().unwrap_or_default(),
+                    description,
+                    description_html,
+                };
+                (locale.clone(), localized)
+            })
+            .collect();
+        self
+    }
+
+    pub fn set_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// replacement: 使用をやめた用語であれば置き換え先 (None なら現役の用語)
+    pub fn set_deprecated(mut self, replacement: Option<String>) -> Self {
+        self.deprecated = replacement.is_some();
+        self.replaced_by = replacement.unwrap_or_default();
+        self
+    }
+
+    pub fn set_rules(mut self, rules: Vec<String>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn set_examples(mut self, examples: Vec<String>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn set_teams(mut self, teams: Vec<String>) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    pub fn set_extra_fields(mut self, extra_fields: BTreeMap<String, String>) -> Self {
+        self.extra_fields = extra_fields;
+        self
+    }
+
+    pub fn set_stereotype(mut self, stereotype: Option<Stereotype>) -> Self {
+        self.stereotype = stereotype.map_or("", |s| s.as_str()).to_string();
+        self.stereotype_label = stereotype.map_or("", |s| s.label()).to_string();
+        self
+    }
+
+    /// 所属する集約ルートの用語を、用語集の行へのリンクにする (set_context の後に呼び出す)
+    pub fn set_aggregate_root(mut self, term_index: &TermIndex, aggregate: Option<&str>) -> Self {
+        self.aggregate_root =
+            aggregate.map(|aggregate| term_link(aggregate, term_index, &self.context));
+        self
+    }
+
+    /// リンク設定に従ってソースコードへの URL を設定する
+    pub fn set_source_link(
+        mut self,
+        link: &LinkConfig,
         file_path: String,
         line_number: usize,
     ) -> Self {
@@ -56,32 +263,200 @@ impl UbiquitousRow {
             .trim_start_matches("./")
             .to_string();
 
-        self.github_url = format!(
-            "https://github.com/{}/blob/{}/{}#L{}",
-            repo, branch, normalized_path, line_number
-        );
+        self.github_url = link.url(&normalized_path, line_number);
         self.file_path = normalized_path;
         self.line_number = line_number;
         self
     }
+
+    /// Lint の検出結果を行に追加し、最も重いレベルを lint_level に反映する
+    pub fn add_lint_message(mut self, lint_message: &LintMessage) -> Self {
+        self.lint_messages.push(format!(
+            "{}: {}",
+            lint_message.level.as_str(),
+            lint_message.message
+        ));
+        if self.lint_level.parse::<Severity>().ok() < Some(lint_message.level) {
+            self.lint_level = lint_message.level.as_str().to_string();
+        }
+        self
+    }
+}
+
+/// 参照を用語集の行へのリンクにする (参照先が見つからなければ anchor は空)
+/// 出力しない用語への参照はリンクにせず、用語名だけを表示する
+fn term_link(reference: &str, term_index: &TermIndex, context: &str) -> TermLink {
+    let anchor = term_index.resolve(reference, Some(context));
+    TermLink {
+        label: TermReference::parse(reference).label(),
+        anchor: anchor
+            .filter(|anchor| !term_index.is_hidden(anchor))
+            .unwrap_or_default()
+            .to_string(),
+        resolved: anchor.is_some(),
+    }
+}
+
+/// 説明文をエスケープし、{@link Term} / [[Term]] をリンクにした HTML と、参照している用語を返す
+fn link_description(
+    description: &str,
+    term_index: &TermIndex,
+    context: &str,
+) -> (String, Vec<TermLink>) {
+    let mut references = Vec::new();
+    let html = split_inline_references(description)
+        .into_iter()
+        .map(|segment| match segment {
+            DescriptionSegment::Text(text) => escape_html(text),
+            DescriptionSegment::Reference { target, label } => {
+                let mut link = term_link(target, term_index, context);
+                if let Some(label) = label {
+                    link.label = label.to_string();
+                }
+                let html = if !link.resolved {
+                    format!(
+                        "<span class=\"unresolved-link\">{}</span>",
+                        escape_html(&link.label)
+                    )
+                } else if link.anchor.is_empty() {
+                    escape_html(&link.label)
+                } else {
+                    format!(
+                        "<a href=\"#{}\">{}</a>",
+                        escape_html(&link.anchor),
+                        escape_html(&link.label)
+                    )
+                };
+                references.push(link);
+                html
+            }
+        })
+        .collect();
+    (html, references)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
 }
 
 #[cfg(test)]
 mod tests {
     use super::UbiquitousRow;
+    use crate::config::ubi_doc_config::{LinkConfig, LinkProvider};
+    use crate::diagnostics::diagnostic::Severity;
+    use crate::lint::lint_message::LintMessage;
+    use crate::parser::{term_index::TermIndex, ubiquitous::Ubiquitous};
 
     #[test]
-    fn test_set_github_url() {
-        let repo = "owner/repo".to_string();
-        let branch = "main".to_string();
-        let file_path = "src/lib.rs".to_string();
-        let line_number = 42;
+    fn test_set_source_link_github() {
+        let link = LinkConfig {
+            repo: Some("owner/repo".to_string()),
+            branch: Some("main".to_string()),
+            ..LinkConfig::default()
+        };
 
-        let row = UbiquitousRow::new().set_github_url(repo, branch, file_path, line_number);
+        let row = UbiquitousRow::new().set_source_link(&link, "src/lib.rs".to_string(), 42);
 
         assert_eq!(
             row.github_url,
             "https://github.com/owner/repo/blob/main/src/lib.rs#L42"
         );
     }
+
+    #[test]
+    fn test_set_source_link_none() {
+        let link = LinkConfig {
+            provider: Some(LinkProvider::None),
+            ..LinkConfig::default()
+        };
+
+        let row = UbiquitousRow::new().set_source_link(&link, "./src/lib.rs".to_string(), 42);
+
+        assert_eq!(row.github_url, "");
+        assert_eq!(row.file_path, "src/lib.rs");
+        assert_eq!(row.line_number, 42);
+    }
+
+    #[test]
+    fn test_add_lint_message() {
+        let warning = LintMessage::new(Severity::Warning, "term-variant", "variant".to_string());
+        let error = LintMessage::new(Severity::Error, "duplicate-term", "dup".to_string());
+
+        let row = UbiquitousRow::new()
+            .add_lint_message(&warning)
+            .add_lint_message(&error)
+            .add_lint_message(&warning);
+
+        assert_eq!(row.lint_level, "error");
+        assert_eq!(row.lint_messages.len(), 3);
+        assert_eq!(row.lint_messages[1], "error: dup");
+    }
+
+    #[test]
+    fn test_set_references() {
+        let terms = vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_context("Sales".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Order Line".to_string())
+                .set_context("Sales".to_string()),
+        ];
+        let term_index = TermIndex::new(&terms);
+
+        let row = UbiquitousRow::new()
+            .set_context("Sales".to_string())
+            .set_description("<b> has [[Order Line]] and {@link Invoice}".to_string())
+            .set_references(&term_index, &["Sales:Order".to_string()]);
+
+        assert_eq!(
+            row.description_html,
+            "&lt;b&gt; has <a href=\"#term-sales-order-line\">Order Line</a> and <span class=\"unresolved-link\">Invoice</span>"
+        );
+        assert_eq!(row.references.len(), 2);
+        assert_eq!(row.references[1].anchor, "");
+        assert_eq!(row.related.len(), 1);
+        assert_eq!(row.related[0].label, "Order");
+        assert_eq!(row.related[0].anchor, "term-sales-order");
+    }
+
+    #[test]
+    fn test_set_references_link_label() {
+        let terms = vec![Ubiquitous::new().set_ubiquitous("Order".to_string())];
+        let term_index = TermIndex::new(&terms);
+
+        let row = UbiquitousRow::new()
+            .set_description("Placed by {@link Order the customer's order}".to_string())
+            .set_references(&term_index, &[]);
+
+        assert_eq!(
+            row.description_html,
+            "Placed by <a href=\"#term-order\">the customer&#39;s order</a>"
+        );
+        assert_eq!(row.references[0].label, "the customer's order");
+        assert!(row.references[0].resolved);
+    }
+
+    #[test]
+    fn test_set_references_hidden_term() {
+        let terms = vec![
+            Ubiquitous::new().set_ubiquitous("Order".to_string()),
+            Ubiquitous::new().set_ubiquitous("Invoice".to_string()),
+        ];
+        let mut term_index = TermIndex::new(&terms);
+        term_index.hide(1);
+
+        let row = UbiquitousRow::new()
+            .set_description("billed by [[Invoice]]".to_string())
+            .set_references(&term_index, &["Invoice".to_string()]);
+
+        // 出力しない用語への参照はリンクにせず、未解決としても扱わない
+        assert_eq!(row.description_html, "billed by Invoice");
+        assert_eq!(row.related[0].anchor, "");
+        assert!(row.related[0].resolved);
+    }
 }
@@ -1,9 +1,20 @@
-use super::ubiquitous_row::UbiquitousRow;
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use super::ubiquitous_row::{TermLink, UbiquitousRow};
 
 pub struct UbiquitousRows {
     pub rows: Vec<UbiquitousRow>,
 }
 
+/// 同じコンテキストの行のまとまり
+#[derive(Serialize)]
+pub struct ContextGroup<'a> {
+    pub context: &'a str,
+    pub rows: Vec<&'a UbiquitousRow>,
+}
+
 impl UbiquitousRows {
     pub fn new() -> Self {
         UbiquitousRows { rows: Vec::new() }
@@ -23,11 +34,113 @@ impl UbiquitousRows {
             }
         });
     }
+
+    /// 列挙子の行を、宣言している enum (Ruby では module) の行の children に移す
+    /// 宣言している enum に用語がない場合は、通常の行として残す
+    pub fn nest_enum_cases(&mut self) {
+        let is_parent = |row: &UbiquitousRow| matches!(row.kind.as_str(), "enum" | "module");
+        let (cases, rows): (Vec<_>, Vec<_>) = std::mem::take(&mut self.rows)
+            .into_iter()
+            .partition(|row| row.kind == "case" && !row.parent.is_empty());
+        self.rows = rows;
+
+        for case in cases {
+            match self
+                .rows
+                .iter_mut()
+                .find(|row| is_parent(row) && row.qualified_name == case.parent)
+            {
+                Some(parent) => parent.children.push(case),
+                None => self.rows.push(case),
+            }
+        }
+        // 列挙子はソースコードでの宣言順に並べる
+        for row in self.rows.iter_mut() {
+            row.children
+                .sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+        }
+    }
+
+    /// 集約に所属する用語の行を、同じコンテキストの集約ルートの行の直後に移し、
+    /// 集約ルートの行には所属する用語へのリンクを追加する (sort 後に呼び出す)
+    /// 集約ルートが見つからない行はそのままの位置に残す
+    pub fn group_by_aggregate(&mut self) {
+        let rows = std::mem::take(&mut self.rows);
+        let root_of: Vec<Option<usize>> = rows
+            .iter()
+            .map(|row| {
+                let root = row
+                    .aggregate_root
+                    .as_ref()
+                    .filter(|root| !root.anchor.is_empty())?;
+                rows.iter().position(|candidate| {
+                    candidate.anchor == root.anchor
+                        && candidate.context == row.context
+                        && candidate.anchor != row.anchor
+                })
+            })
+            .collect();
+        // 集約ルート自身がほかの集約に所属している場合はまとめない
+        let root_of: Vec<Option<usize>> = root_of
+            .iter()
+            .map(|root| root.filter(|root| root_of[*root].is_none()))
+            .collect();
+
+        let mut members: Vec<Vec<UbiquitousRow>> = rows.iter().map(|_| Vec::new()).collect();
+        let mut others = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            match root_of[index] {
+                Some(root) => members[root].push(row),
+                None => others.push((index, row)),
+            }
+        }
+        for (index, mut row) in others {
+            let group = std::mem::take(&mut members[index]);
+            row.aggregate_members = group
+                .iter()
+                .map(|member| TermLink {
+                    label: member.ubiquitous.clone(),
+                    anchor: member.anchor.clone(),
+                    resolved: true,
+                })
+                .collect();
+            self.rows.push(row);
+            self.rows.extend(group);
+        }
+    }
+
+    /// 翻訳があるロケールの一覧 (列挙子の行も含む。名前順)
+    pub fn locales(&self) -> Vec<&str> {
+        let locales: BTreeSet<&str> = self
+            .rows
+            .iter()
+            .flat_map(|row| std::iter::once(row).chain(row.children.iter()))
+            .flat_map(|row| row.translations.keys().map(String::as_str))
+            .collect();
+        locales.into_iter().collect()
+    }
+
+    /// 連続する同じコンテキストの行をまとめる (sort 後に呼び出す)
+    pub fn group_by_context(&self) -> Vec<ContextGroup<'_>> {
+        let mut groups: Vec<ContextGroup> = Vec::new();
+        for row in self.rows.iter() {
+            match groups.last_mut() {
+                Some(group) if group.context == row.context => group.rows.push(row),
+                _ => groups.push(ContextGroup {
+                    context: &row.context,
+                    rows: vec![row],
+                }),
+            }
+        }
+        groups
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ubi_doc_config::LinkConfig;
+    use crate::parser::{term_index::TermIndex, ubiquitous::Ubiquitous};
 
     #[test]
     fn test_sort_by_context_and_ubiquitous() {
@@ -43,6 +156,7 @@ mod tests {
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            ..UbiquitousRow::new()
         };
         let row2 = UbiquitousRow {
             class_name: "C2".to_string(),
@@ -52,6 +166,7 @@ mod tests {
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            ..UbiquitousRow::new()
         };
         let row3 = UbiquitousRow {
             class_name: "C3".to_string(),
@@ -61,6 +176,7 @@ mod tests {
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            ..UbiquitousRow::new()
         };
         let row4 = UbiquitousRow {
             class_name: "C4".to_string(),
@@ -70,6 +186,7 @@ mod tests {
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            ..UbiquitousRow::new()
         };
         let row5 = UbiquitousRow {
             class_name: "C5".to_string(),
@@ -79,6 +196,7 @@ mod tests {
             file_path: "".to_string(),
             line_number: 0,
             github_url: "".to_string(),
+            ..UbiquitousRow::new()
         };
 
         // ランダムな順序で追加
@@ -116,4 +234,120 @@ mod tests {
         assert_eq!(rows.rows[4].context, "B");
         assert_eq!(rows.rows[4].ubiquitous, "z");
     }
+
+    #[test]
+    fn test_nest_enum_cases() {
+        let mut rows = UbiquitousRows::new();
+        rows.add(
+            UbiquitousRow::new()
+                .set_ubiquitous("注文ステータス".to_string())
+                .set_kind("enum".to_string())
+                .set_qualified_name("Status".to_string()),
+        );
+        for (ubiquitous, parent, line_number) in [
+            ("発送済み", "Status", 8),
+            ("保留中", "Status", 4),
+            ("不明", "Unknown", 2),
+        ] {
+            rows.add(
+                UbiquitousRow::new()
+                    .set_ubiquitous(ubiquitous.to_string())
+                    .set_kind("case".to_string())
+                    .set_parent(parent.to_string())
+                    .set_source_link(
+                        &LinkConfig {
+                            repo: Some("owner/repo".to_string()),
+                            branch: Some("main".to_string()),
+                            ..LinkConfig::default()
+                        },
+                        "Status.java".to_string(),
+                        line_number,
+                    ),
+            );
+        }
+        rows.sort();
+
+        rows.nest_enum_cases();
+
+        // 宣言している enum の用語がない列挙子は通常の行のまま
+        assert_eq!(rows.rows.len(), 2);
+        let status = &rows.rows[0];
+        assert_eq!(status.ubiquitous, "注文ステータス");
+        assert_eq!(status.children.len(), 2);
+        assert_eq!(status.children[0].ubiquitous, "保留中");
+        assert_eq!(status.children[1].ubiquitous, "発送済み");
+        assert_eq!(rows.rows[1].ubiquitous, "不明");
+    }
+
+    #[test]
+    fn test_group_by_context() {
+        let mut rows = UbiquitousRows::new();
+        for (context, ubiquitous) in [("Billing", "Invoice"), ("", "Money"), ("Billing", "Bill")] {
+            rows.add(
+                UbiquitousRow::new()
+                    .set_context(context.to_string())
+                    .set_ubiquitous(ubiquitous.to_string()),
+            );
+        }
+        rows.sort();
+
+        let groups = rows.group_by_context();
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].context, "");
+        assert_eq!(groups[0].rows.len(), 1);
+        assert_eq!(groups[1].context, "Billing");
+        assert_eq!(groups[1].rows[0].ubiquitous, "Bill");
+        assert_eq!(groups[1].rows[1].ubiquitous, "Invoice");
+    }
+
+    #[test]
+    fn test_group_by_aggregate() {
+        let term_index = TermIndex::new(&[
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_context("Sales".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Invoice".to_string())
+                .set_context("Billing".to_string()),
+        ]);
+        let mut rows = UbiquitousRows::new();
+        for (ubiquitous, context, aggregate) in [
+            ("Order", "Sales", None),
+            ("Amount", "Sales", Some("Order")),
+            ("Order Line", "Sales", Some("Order")),
+            ("Address", "Sales", Some("Unknown")),
+            ("Line", "Billing", Some("Sales:Order")),
+        ] {
+            rows.add(
+                UbiquitousRow::new()
+                    .set_anchor(format!("term-{}", ubiquitous))
+                    .set_ubiquitous(ubiquitous.to_string())
+                    .set_context(context.to_string())
+                    .set_aggregate_root(&term_index, aggregate),
+            );
+        }
+        // 索引と同じ id にする
+        rows.rows[0].anchor = "term-sales-order".to_string();
+        rows.sort();
+
+        rows.group_by_aggregate();
+
+        let order: Vec<_> = rows
+            .rows
+            .iter()
+            .map(|row| row.ubiquitous.as_str())
+            .collect();
+        // 集約ルートが見つからない行と、別のコンテキストの行はそのまま
+        assert_eq!(
+            order,
+            vec!["Line", "Address", "Order", "Amount", "Order Line"]
+        );
+        let members: Vec<_> = rows.rows[2]
+            .aggregate_members
+            .iter()
+            .map(|link| link.label.as_str())
+            .collect();
+        assert_eq!(members, vec!["Amount", "Order Line"]);
+    }
 }
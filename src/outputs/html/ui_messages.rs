@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+
+/// --ui-lang を指定しない場合の言語
+pub const DEFAULT_UI_LANG: &str = "en";
+
+const EN_MESSAGES: &str = include_str!("messages/en.toml");
+const JA_MESSAGES: &str = include_str!("messages/ja.toml");
+
+/// HTML の画面に表示する文言
+/// 組み込みの英語・日本語のカタログと、ユーザーが用意したカタログファイルから作る
+#[derive(Debug, Serialize)]
+pub struct UiMessages {
+    /// <html lang> に使う言語
+    pub lang: String,
+    pub messages: BTreeMap<String, String>,
+}
+
+impl UiMessages {
+    /// 組み込みのカタログ (en / ja)
+    /// 日本語のカタログにない文言は英語で表示する
+    pub fn builtin(lang: &str) -> Option<Self> {
+        let mut messages = parse_catalog(EN_MESSAGES).expect("built-in catalog is valid");
+        match lang {
+            "en" => {}
+            "ja" => messages.extend(parse_catalog(JA_MESSAGES).expect("built-in catalog is valid")),
+            _ => return None,
+        }
+        Some(UiMessages {
+            lang: lang.to_string(),
+            messages,
+        })
+    }
+
+    /// 設定の ui-lang / ui-catalog から文言を作る
+    /// カタログファイルに書かれた文言は組み込みのカタログより優先し、書かれていない文言は
+    /// 組み込みのカタログ (ui-lang が en / ja 以外なら英語) で表示する
+    pub fn from_config(config: &UbiDocConfig) -> Result<Self, UbiDocError> {
+        let lang = config.ui_lang.as_deref().unwrap_or(DEFAULT_UI_LANG);
+        let Some(catalog_path) = config.ui_catalog.as_deref() else {
+            return UiMessages::builtin(lang).ok_or_else(|| {
+                UbiDocError::Config(format!(
+                    "Unknown UI language \"{}\" (built-in: en, ja). Use --ui-catalog to provide a message catalog",
+                    lang
+                ))
+            });
+        };
+
+        let mut ui_messages = UiMessages::builtin(lang).unwrap_or_else(|| UiMessages {
+            lang: lang.to_string(),
+            ..UiMessages::default()
+        });
+        ui_messages
+            .messages
+            .extend(load_catalog(Path::new(catalog_path))?);
+        Ok(ui_messages)
+    }
+}
+
+impl Default for UiMessages {
+    fn default() -> Self {
+        UiMessages::builtin(DEFAULT_UI_LANG).expect("default UI language is built in")
+    }
+}
+
+fn load_catalog(path: &Path) -> Result<BTreeMap<String, String>, UbiDocError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| UbiDocError::Config(format!("Failed to read UI catalog {:?}: {}", path, e)))?;
+    parse_catalog(&content)
+        .map_err(|e| UbiDocError::Config(format!("Failed to parse UI catalog {:?}: {}", path, e)))
+}
+
+fn parse_catalog(content: &str) -> Result<BTreeMap<String, String>, toml::de::Error> {
+    toml::from_str(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_builtin() {
+        let en = UiMessages::builtin("en").unwrap();
+        let ja = UiMessages::builtin("ja").unwrap();
+
+        assert_eq!(en.messages["title"], "Ubiquitous Language");
+        assert_eq!(ja.messages["title"], "ユビキタス言語");
+        assert_eq!(ja.lang, "ja");
+        // 日本語のカタログにもすべての文言がある
+        assert_eq!(
+            en.messages.keys().collect::<Vec<_>>(),
+            ja.messages.keys().collect::<Vec<_>>()
+        );
+        assert!(UiMessages::builtin("fr").is_none());
+    }
+
+    #[test]
+    fn test_from_config_custom_catalog() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let catalog_path = dir.path().join("fr.toml");
+        fs::write(&catalog_path, "title = \"Langage omniprésent\"\n")?;
+        let config = UbiDocConfig {
+            ui_lang: Some("fr".to_string()),
+            ui_catalog: Some(catalog_path.to_string_lossy().to_string()),
+            ..UbiDocConfig::default()
+        };
+
+        let ui_messages = UiMessages::from_config(&config)?;
+
+        assert_eq!(ui_messages.lang, "fr");
+        assert_eq!(ui_messages.messages["title"], "Langage omniprésent");
+        // カタログにない文言は英語で表示する
+        assert_eq!(ui_messages.messages["column_context"], "Context");
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_config_unknown_lang() {
+        let config = UbiDocConfig {
+            ui_lang: Some("fr".to_string()),
+            ..UbiDocConfig::default()
+        };
+
+        assert!(matches!(
+            UiMessages::from_config(&config),
+            Err(UbiDocError::Config(_))
+        ));
+    }
+}
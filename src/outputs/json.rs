@@ -0,0 +1 @@
+pub mod generate_json;
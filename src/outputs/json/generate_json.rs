@@ -0,0 +1,63 @@
+use std::{fs, path::Path};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::html::{output_assets::create_dir, ubiquitous_rows::UbiquitousRows};
+
+/// 用語一覧を JSON として出力する
+pub fn generate_json(
+    ubiquitous_rows: &UbiquitousRows,
+    output_path: &Path,
+) -> Result<(), UbiDocError> {
+    let json = render_json(ubiquitous_rows)?;
+
+    create_dir(output_path)?;
+    let path = output_path.join("ubiquitous.json");
+    fs::write(&path, json).map_err(|source| UbiDocError::Write { path, source })
+}
+
+pub fn render_json(ubiquitous_rows: &UbiquitousRows) -> Result<String, UbiDocError> {
+    Ok(serde_json::to_string_pretty(&ubiquitous_rows.rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::html::ubiquitous_row::UbiquitousRow;
+    use crate::parser::{term_index::TermIndex, translation::Translation};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_render_json() {
+        let row = UbiquitousRow::new()
+            .set_class_name("Order".to_string())
+            .set_ubiquitous("Order".to_string())
+            .set_context("Sales".to_string())
+            .set_rules(vec!["Cannot ship before payment.".to_string()])
+            .set_examples(vec!["Two books for Alice.".to_string()])
+            .set_extra_fields(BTreeMap::from([(
+                "jira".to_string(),
+                "SALES-12".to_string(),
+            )]))
+            .set_translations(
+                &TermIndex::new(&[]),
+                &BTreeMap::from([(
+                    "ja".to_string(),
+                    Translation {
+                        ubiquitous: Some("注文".to_string()),
+                        description: None,
+                    },
+                )]),
+            );
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&rows).unwrap()).unwrap();
+
+        assert_eq!(json[0]["ubiquitous"], "Order");
+        assert_eq!(json[0]["class_name"], "Order");
+        assert_eq!(json[0]["context"], "Sales");
+        assert_eq!(json[0]["rules"][0], "Cannot ship before payment.");
+        assert_eq!(json[0]["examples"][0], "Two books for Alice.");
+        assert_eq!(json[0]["translations"]["ja"]["ubiquitous"], "注文");
+        assert_eq!(json[0]["extra_fields"]["jira"], "SALES-12");
+    }
+}
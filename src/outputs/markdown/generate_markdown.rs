@@ -0,0 +1,261 @@
+use std::{fs, path::Path};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::outputs::html::{
+    output_assets::create_dir, ubiquitous_row::UbiquitousRow, ubiquitous_rows::UbiquitousRows,
+};
+use crate::parser::term_reference::plain_description;
+
+/// 用語一覧を Markdown の表として出力する
+pub fn generate_markdown(
+    ubiquitous_rows: &UbiquitousRows,
+    output_path: &Path,
+    extra_fields: &[String],
+) -> Result<(), UbiDocError> {
+    let markdown = render_markdown(ubiquitous_rows, extra_fields);
+
+    create_dir(output_path)?;
+    let path = output_path.join("ubiquitous.md");
+    fs::write(&path, markdown).map_err(|source| UbiDocError::Write { path, source })
+}
+
+/// 設定で追加したタグは、設定に書かれた順に Description と URL の間に列として追加する
+pub fn render_markdown(ubiquitous_rows: &UbiquitousRows, extra_fields: &[String]) -> String {
+    let mut headers = vec![
+        "Ubiquitous",
+        "Class Name",
+        "Kind",
+        "Stereotype",
+        "Declared In",
+        "Qualified Name",
+        "Context",
+        "Team",
+        "Description",
+    ];
+    headers.extend(extra_fields.iter().map(String::as_str));
+    headers.push("URL");
+    let headers: Vec<String> = headers.into_iter().map(escape).collect();
+
+    let mut lines = vec![
+        "# Ubiquitous Language".to_string(),
+        String::new(),
+        format!("| {} |", headers.join(" | ")),
+        format!("|{}", " --- |".repeat(headers.len())),
+    ];
+
+    for row in &ubiquitous_rows.rows {
+        lines.push(render_row(row, "", extra_fields));
+        // enum の列挙子は enum の行の直後に字下げして並べる
+        for child in &row.children {
+            lines.push(render_row(child, "↳ ", extra_fields));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn render_row(row: &UbiquitousRow, prefix: &str, extra_fields: &[String]) -> String {
+    let location = format!("{}:{}", row.file_path, row.line_number);
+    let url = if row.github_url.is_empty() {
+        location
+    } else {
+        format!("[{}]({})", location, row.github_url)
+    };
+    // 翻訳はロケールを付けて既定の用語名・説明の後ろに並べる
+    let mut ubiquitous = row.ubiquitous.clone();
+    let mut description = plain_description(&row.description);
+    for (locale, translation) in row.translations.iter() {
+        if !translation.ubiquitous.is_empty() {
+            ubiquitous.push_str(&format!("\n{}: {}", locale, translation.ubiquitous));
+        }
+        if !translation.description.is_empty() {
+            if !description.is_empty() {
+                description.push('\n');
+            }
+            description.push_str(&format!(
+                "{}: {}",
+                locale,
+                plain_description(&translation.description)
+            ));
+        }
+    }
+    // 参照は用語名だけにし、関連する用語は説明の後ろに並べる
+    // 不変条件・業務ルールと使用例は箇条書きにする (セル内の改行は <br> になる)
+    for (heading, items) in [("Rules", &row.rules), ("Examples", &row.examples)] {
+        if items.is_empty() {
+            continue;
+        }
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        description.push_str(&format!("{}:", heading));
+        for item in items {
+            description.push_str(&format!("\n- {}", item.replace('\n', " ")));
+        }
+    }
+    if !row.related.is_empty() {
+        let related: Vec<_> = row.related.iter().map(|link| link.label.as_str()).collect();
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        description.push_str(&format!("See also: {}", related.join(", ")));
+    }
+    let mut cells = vec![
+        format!("{}{}", prefix, escape(&ubiquitous)),
+        escape(&row.class_name),
+        escape(&row.kind),
+        escape(&row.stereotype_label),
+        escape(&row.parent),
+        escape(&row.qualified_name),
+        escape(&row.context),
+        escape(&row.teams.join(", ")),
+        escape(&description),
+    ];
+    for name in extra_fields {
+        cells.push(escape(
+            row.extra_fields.get(name).map_or("", String::as_str),
+        ));
+    }
+    cells.push(url);
+    format!("| {} |", cells.join(" | "))
+}
+
+/// 表のセルを壊さないように `|` と改行をエスケープする
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ubi_doc_config::LinkConfig;
+    use crate::parser::{stereotype::Stereotype, term_index::TermIndex, translation::Translation};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_render_markdown() {
+        let row = UbiquitousRow::new()
+            .set_class_name("Order".to_string())
+            .set_qualified_name(r"App\Sales\Order".to_string())
+            .set_kind("enum".to_string())
+            .set_stereotype(Some(Stereotype::Aggregate))
+            .set_ubiquitous("Order".to_string())
+            .set_context("Sales".to_string())
+            .set_teams(vec!["@acme/sales".to_string(), "@acme/billing".to_string()])
+            .set_description("A | B".to_string())
+            .set_source_link(
+                &LinkConfig {
+                    repo: Some("owner/repo".to_string()),
+                    branch: Some("main".to_string()),
+                    ..LinkConfig::default()
+                },
+                "src/Order.php".to_string(),
+                3,
+            );
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let markdown = render_markdown(&rows, &[]);
+
+        assert!(markdown.starts_with("# Ubiquitous Language\n"));
+        assert!(markdown.contains(
+            r"| Order | Order | enum | Aggregate |  | App\Sales\Order | Sales | @acme/sales, @acme/billing | A \| B | [src/Order.php:3](https://github.com/owner/repo/blob/main/src/Order.php#L3) |"
+        ));
+    }
+
+    #[test]
+    fn test_render_markdown_enum_cases() {
+        let mut status = UbiquitousRow::new()
+            .set_ubiquitous("Order Status".to_string())
+            .set_kind("enum".to_string());
+        status.children.push(
+            UbiquitousRow::new()
+                .set_ubiquitous("Pending".to_string())
+                .set_class_name("PENDING".to_string())
+                .set_kind("case".to_string())
+                .set_parent("Status".to_string()),
+        );
+        let rows = UbiquitousRows { rows: vec![status] };
+
+        let markdown = render_markdown(&rows, &[]);
+
+        let lines: Vec<_> = markdown.lines().collect();
+        assert!(lines[4].starts_with("| Order Status | "));
+        assert!(lines[5].starts_with("| ↳ Pending | PENDING | case |  | Status | "));
+    }
+
+    #[test]
+    fn test_render_markdown_rules_and_examples() {
+        let row = UbiquitousRow::new()
+            .set_ubiquitous("Order".to_string())
+            .set_description("A purchase.".to_string())
+            .set_rules(vec![
+                "Cannot ship before payment.".to_string(),
+                "At least one\nline.".to_string(),
+            ])
+            .set_examples(vec!["Two books for Alice.".to_string()]);
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let markdown = render_markdown(&rows, &[]);
+
+        assert!(markdown.contains(
+            "| A purchase.<br>Rules:<br>- Cannot ship before payment.<br>- At least one line.<br>Examples:<br>- Two books for Alice. |"
+        ));
+    }
+
+    #[test]
+    fn test_render_markdown_extra_fields() {
+        let row1 = UbiquitousRow::new()
+            .set_ubiquitous("Order".to_string())
+            .set_extra_fields(BTreeMap::from([
+                ("jira".to_string(), "SALES-12".to_string()),
+                ("status".to_string(), "draft\nneeds review".to_string()),
+            ]));
+        let row2 = UbiquitousRow::new()
+            .set_ubiquitous("Invoice".to_string())
+            .set_extra_fields(BTreeMap::from([(
+                "status".to_string(),
+                "approved".to_string(),
+            )]));
+        let rows = UbiquitousRows {
+            rows: vec![row1, row2],
+        };
+
+        let extra_fields = [
+            "status".to_string(),
+            "jira".to_string(),
+            "reviewer".to_string(),
+        ];
+
+        let markdown = render_markdown(&rows, &extra_fields);
+
+        // 設定に書かれた順に、どの行にも値がない列も含めて追加する
+        assert!(markdown.contains(
+            "| Ubiquitous | Class Name | Kind | Stereotype | Declared In | Qualified Name | Context | Team | Description | status | jira | reviewer | URL |\n| --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- | --- |\n"
+        ));
+        assert!(markdown.contains(" | draft<br>needs review | SALES-12 |  | :0 |"));
+        // 値がない列は空欄にする
+        assert!(markdown.contains("| Invoice |  |  |  |  |  |  |  |  | approved |  |  | :0 |"));
+    }
+
+    #[test]
+    fn test_render_markdown_translations() {
+        let term_index = TermIndex::new(&[]);
+        let translations = BTreeMap::from([(
+            "ja".to_string(),
+            Translation {
+                ubiquitous: Some("注文".to_string()),
+                description: Some("顧客による購入。".to_string()),
+            },
+        )]);
+        let row = UbiquitousRow::new()
+            .set_ubiquitous("Order".to_string())
+            .set_description("A purchase.".to_string())
+            .set_translations(&term_index, &translations);
+        let rows = UbiquitousRows { rows: vec![row] };
+
+        let markdown = render_markdown(&rows, &[]);
+
+        assert!(markdown.contains("| Order<br>ja: 注文 | "));
+        assert!(markdown.contains(" | A purchase.<br>ja: 顧客による購入。 | "));
+    }
+}
@@ -1,7 +1,21 @@
-mod file_io;
+pub mod class_doc;
+pub mod code_owners;
+pub mod context_resolver;
+pub mod doc_comment;
+pub mod extract_ubiquitous;
+pub mod file_io;
+pub mod get_class_declaration_list;
 pub mod get_ubiquitous_list;
 mod java;
 mod kotlin;
+pub mod owner_resolver;
+pub mod parent_chain;
 mod php;
 mod ruby;
+pub mod scope;
+pub mod stereotype;
+pub mod term_index;
+pub mod term_reference;
+pub mod translation;
+pub mod type_references;
 pub mod ubiquitous;
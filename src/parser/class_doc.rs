@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use crate::config::ubi_doc_config::TagConfig;
+use crate::parser::doc_comment::{CommentStyle, DocComment};
+
+/// 宣言の種類
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DeclarationKind {
+    #[default]
+    Class,
+    Interface,
+    Enum,
+    Record,
+    Trait,
+    Object,
+    Module,
+    /// メソッド・関数 (ドメインの操作)
+    Operation,
+    /// フィールド・プロパティ (値オブジェクトの属性など)
+    Property,
+    /// 定数
+    Constant,
+    /// enum の列挙子 (Ruby では module 直下の定数)
+    EnumCase,
+}
+
+impl DeclarationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeclarationKind::Class => "class",
+            DeclarationKind::Interface => "interface",
+            DeclarationKind::Enum => "enum",
+            DeclarationKind::Record => "record",
+            DeclarationKind::Trait => "trait",
+            DeclarationKind::Object => "object",
+            DeclarationKind::Module => "module",
+            DeclarationKind::Operation => "operation",
+            DeclarationKind::Property => "property",
+            DeclarationKind::Constant => "constant",
+            DeclarationKind::EnumCase => "case",
+        }
+    }
+
+    /// 型の宣言 (クラス・インターフェース・enum など、メンバー以外) の種類
+    pub const TYPE_KINDS: [DeclarationKind; 7] = [
+        DeclarationKind::Class,
+        DeclarationKind::Interface,
+        DeclarationKind::Enum,
+        DeclarationKind::Record,
+        DeclarationKind::Trait,
+        DeclarationKind::Object,
+        DeclarationKind::Module,
+    ];
+
+    /// クラスに属するメンバー (メソッド・プロパティ・定数・列挙子) かどうか
+    pub fn is_member(&self) -> bool {
+        matches!(
+            self,
+            DeclarationKind::Operation
+                | DeclarationKind::Property
+                | DeclarationKind::Constant
+                | DeclarationKind::EnumCase
+        )
+    }
+}
+
+impl FromStr for DeclarationKind {
+    type Err = String;
+
+    /// 型の宣言の種類を as_str の名前から戻す (--coverage-kind で使う)
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        DeclarationKind::TYPE_KINDS
+            .into_iter()
+            .find(|type_kind| type_kind.as_str() == kind)
+            .ok_or_else(|| {
+                let names: Vec<_> = DeclarationKind::TYPE_KINDS
+                    .iter()
+                    .map(DeclarationKind::as_str)
+                    .collect();
+                format!(
+                    "unknown kind \"{}\" (expected one of: {})",
+                    kind,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// (クラス名, Docコメント) を格納するための型
+#[derive(Debug)]
+pub struct ClassDoc {
+    pub class_name: String,
+    pub kind: DeclarationKind,
+    /// 名前空間・外側のクラスを含めた完全修飾名
+    pub qualified_name: String,
+    /// メソッド・列挙子などを宣言しているクラスの完全修飾名
+    pub parent: Option<String>,
+    pub doc_comment: String,
+    pub doc_comment_line: Option<usize>,
+    /// クラスが属するパッケージ・名前空間・モジュール
+    pub namespace: Option<String>,
+    /// クラスの中で使われている型名 (メソッドなどのメンバーでは空)
+    pub type_references: Vec<String>,
+}
+
+/// Docコメントの有無にかかわらず、見つかったクラス宣言 (インターフェース・enum・メソッドなども含む) を格納するための型
+#[derive(Debug)]
+pub struct ClassDeclaration {
+    pub class_name: String,
+    /// クラス・インターフェース・enum などの種類
+    pub kind: DeclarationKind,
+    /// 名前空間・外側のクラスを含めた完全修飾名
+    pub qualified_name: String,
+    /// メソッド・列挙子などを宣言しているクラスの完全修飾名 (クラス自体やトップレベルの関数では None)
+    pub parent: Option<String>,
+    pub line_number: usize,
+    pub doc_comment: Option<(String, usize)>,
+    /// クラスが属するパッケージ・名前空間・モジュール
+    pub namespace: Option<String>,
+    /// クラスの中 (継承・フィールド・引数・生成など) で使われている型名
+    pub type_references: Vec<String>,
+}
+
+impl ClassDeclaration {
+    /// Docコメントが付いている場合のみ ClassDoc に変換する
+    pub fn into_class_doc(self) -> Option<ClassDoc> {
+        self.doc_comment
+            .map(|(doc_comment, doc_comment_line)| ClassDoc {
+                class_name: self.class_name,
+                kind: self.kind,
+                qualified_name: self.qualified_name,
+                parent: self.parent,
+                doc_comment,
+                doc_comment_line: Some(doc_comment_line),
+                namespace: self.namespace,
+                type_references: self.type_references,
+            })
+    }
+
+    /// 用語のタグ (既定は @ubiquitous) を含む Docコメントが付いているかどうか
+    pub fn has_ubiquitous(&self, tags: &TagConfig, style: CommentStyle) -> bool {
+        self.has_tag(&tags.ubiquitous, tags, style)
+    }
+
+    /// 指定した名前のタグを含む Docコメントが付いているかどうか (別名のタグも含める)
+    pub fn has_tag(&self, name: &str, tags: &TagConfig, style: CommentStyle) -> bool {
+        self.doc_comment
+            .as_ref()
+            .is_some_and(|(doc_comment, line)| {
+                let mut doc = DocComment::parse(doc_comment, style, *line);
+                doc.resolve_aliases(&tags.aliases);
+                doc.tags.iter().any(|tag| tag.name == name)
+            })
+    }
+}
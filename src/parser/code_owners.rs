@@ -0,0 +1,221 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+
+/// ディレクトリの中で CODEOWNERS を探す場所 (GitHub と同じ順)
+const CANDIDATES: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// CODEOWNERS ファイルの内容
+/// パターンはファイルを置いたリポジトリのルートからの相対パスとして扱い、後に書かれた行を優先する
+#[derive(Debug)]
+pub struct CodeOwners {
+    pub path: PathBuf,
+    globs: GlobSet,
+    /// glob ごとの、何番目の行のパターンか
+    rule_indices: Vec<usize>,
+    /// 行ごとの所有者 (所有者を書かない行は所有者なしとする)
+    owners: Vec<Vec<String>>,
+}
+
+impl CodeOwners {
+    /// dir の .github/CODEOWNERS、CODEOWNERS、docs/CODEOWNERS の順に探す
+    pub fn discover(dir: &Path) -> Option<PathBuf> {
+        CANDIDATES
+            .iter()
+            .map(|candidate| dir.join(candidate))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, UbiDocError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            UbiDocError::Config(format!("Failed to read CODEOWNERS {:?}: {}", path, e))
+        })?;
+        // .github/ と docs/ に置いた場合はその親ディレクトリをルートとする
+        // 相対パスと絶対パスのどちらで入力ディレクトリを指定しても一致するように、絶対パスにする
+        let path_buf = canonicalize(path);
+        let parent = path_buf.parent().unwrap_or(Path::new(""));
+        let root = match parent.file_name().and_then(|name| name.to_str()) {
+            Some(".github" | "docs") => parent.parent().unwrap_or(Path::new("")),
+            _ => parent,
+        };
+        let mut code_owners = CodeOwners::parse(&content, root)?;
+        code_owners.path = path.to_path_buf();
+        Ok(code_owners)
+    }
+
+    pub fn parse(content: &str, root: &Path) -> Result<Self, UbiDocError> {
+        let root = normalize(&root.to_string_lossy());
+        let mut builder = GlobSetBuilder::new();
+        let mut rule_indices = Vec::new();
+        let mut owners = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            // コメントと GitLab のセクション見出し ([Section] / ^[Section]) は読み飛ばす
+            if line.is_empty() || line.starts_with('#') || line.starts_with(['[', '^']) {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let Some(pattern) = words.next() else {
+                continue;
+            };
+            for glob in to_globs(pattern) {
+                let glob = GlobBuilder::new(&root.join(glob).to_string_lossy())
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| {
+                        UbiDocError::Config(format!("Invalid CODEOWNERS pattern: {}", e))
+                    })?;
+                builder.add(glob);
+                rule_indices.push(owners.len());
+            }
+            owners.push(
+                words
+                    .take_while(|word| !word.starts_with('#'))
+                    .map(str::to_string)
+                    .collect(),
+            );
+        }
+
+        let globs = builder
+            .build()
+            .map_err(|e| UbiDocError::Config(format!("Invalid CODEOWNERS pattern: {}", e)))?;
+        Ok(CodeOwners {
+            path: PathBuf::new(),
+            globs,
+            rule_indices,
+            owners,
+        })
+    }
+
+    /// ファイルの所有者 (一致する行がなければ None、所有者なしの行に一致した場合は空)
+    pub fn owners_of(&self, file_path: &str) -> Option<&[String]> {
+        let file_path = canonicalize(Path::new(file_path));
+        self.globs
+            .matches(normalize(&file_path.to_string_lossy()))
+            .into_iter()
+            .map(|index| self.rule_indices[index])
+            .max()
+            .map(|rule| self.owners[rule].as_slice())
+    }
+}
+
+/// CODEOWNERS のパターンを glob にする
+/// - "/" で始まるか途中に "/" を含むパターンはルートからのパス、それ以外はどの階層にも一致する
+/// - "/" で終わるパターンはディレクトリの配下に、それ以外はファイル自身とディレクトリの配下に一致する
+/// - 最後の要素にワイルドカードを含むパターン (docs/* など) は、その階層のファイルだけに一致する
+fn to_globs(pattern: &str) -> Vec<String> {
+    let is_dir = pattern.ends_with('/');
+    let path = pattern.trim_matches('/');
+    let is_anchored = pattern.starts_with('/') || path.contains('/');
+    let has_wildcard = path
+        .rsplit('/')
+        .next()
+        .is_some_and(|last| last.contains('*'));
+    let path = if is_anchored {
+        path.to_string()
+    } else {
+        format!("**/{}", path)
+    };
+    if is_dir {
+        vec![format!("{}/**", path)]
+    } else if has_wildcard {
+        vec![path]
+    } else {
+        vec![path.clone(), format!("{}/**", path)]
+    }
+}
+
+/// 存在するパスは絶対パスにする (シンボリックリンクや ".." も解決する)
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// "./" を取り除き、区切り文字を揃える
+fn normalize(path: &str) -> PathBuf {
+    Path::new(&path.replace('\\', "/"))
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use tempfile::tempdir;
+
+    const CODEOWNERS: &str = r#"
+# 全体の既定の所有者
+*                   @acme/architects
+*.rb                @acme/ruby-team
+/services/billing/  @acme/billing  billing@example.com
+services/ordering   @acme/ordering # 末尾のコメント
+docs/*              @acme/writers
+/services/billing/legacy/
+
+[Section]
+"#;
+
+    #[test]
+    fn test_owners_of() {
+        let code_owners = CodeOwners::parse(CODEOWNERS, Path::new("repo")).unwrap();
+        let owners = |file_path: &str| code_owners.owners_of(file_path).map(<[String]>::to_vec);
+
+        assert_eq!(
+            owners("repo/lib/Util.php"),
+            Some(vec!["@acme/architects".to_string()])
+        );
+        assert_eq!(
+            owners("./repo/app/models/order.rb"),
+            Some(vec!["@acme/ruby-team".to_string()])
+        );
+        // 後に書かれた行が優先される
+        assert_eq!(
+            owners("repo/services/billing/Invoice.kt"),
+            Some(vec![
+                "@acme/billing".to_string(),
+                "billing@example.com".to_string()
+            ])
+        );
+        assert_eq!(
+            owners("repo/services/ordering/domain/Order.java"),
+            Some(vec!["@acme/ordering".to_string()])
+        );
+        // docs/* は直下のファイルだけに一致する
+        assert_eq!(
+            owners("repo/docs/guide.md"),
+            Some(vec!["@acme/writers".to_string()])
+        );
+        assert_eq!(
+            owners("repo/docs/api/Order.php"),
+            Some(vec!["@acme/architects".to_string()])
+        );
+        // 所有者を書かない行は所有者なし
+        assert_eq!(owners("repo/services/billing/legacy/Old.php"), Some(vec![]));
+        // ルートの外のファイル
+        assert_eq!(owners("other/Order.php"), None);
+    }
+
+    #[test]
+    fn test_load_from_github_dir() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::create_dir(dir.path().join(".github"))?;
+        fs::write(dir.path().join(".github/CODEOWNERS"), "/src/ @acme/sales\n")?;
+        fs::write(dir.path().join("CODEOWNERS"), "* @acme/other\n")?;
+
+        let path = CodeOwners::discover(dir.path()).unwrap();
+        let code_owners = CodeOwners::load(&path)?;
+
+        assert_eq!(path, dir.path().join(".github/CODEOWNERS"));
+        // .github/ に置いた場合もリポジトリのルートからのパスとして扱う
+        let file_path = dir.path().join("src/Order.php");
+        assert_eq!(
+            code_owners.owners_of(&file_path.to_string_lossy()),
+            Some(["@acme/sales".to_string()].as_slice())
+        );
+        Ok(())
+    }
+}
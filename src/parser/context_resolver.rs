@@ -0,0 +1,327 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use std::cmp::Reverse;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::parser::parent_chain::find_in_parents;
+use crate::parser::ubiquitous::Ubiquitous;
+
+/// @context が書かれていない用語のコンテキストを決める
+/// 1. メソッド・列挙子などは、宣言しているクラスに書かれた @context
+/// 2. [[namespace-contexts]] のうち、パッケージ・名前空間に最初に一致したもの
+/// 3. [[contexts]] のうち、ファイルのパスに一致した最も具体的なもの
+#[derive(Debug)]
+pub struct ContextResolver {
+    globs: GlobSet,
+    contexts: Vec<String>,
+    /// [[contexts]] ごとの、パターンに含まれるワイルドカードのない階層の数
+    specificities: Vec<usize>,
+    namespace_rules: Vec<(Regex, String)>,
+}
+
+impl ContextResolver {
+    pub fn from_config(config: &UbiDocConfig) -> Result<Self, UbiDocError> {
+        let mut builder = GlobSetBuilder::new();
+        for mapping in config.contexts.iter() {
+            let path = normalize(&mapping.path).to_string_lossy().to_string();
+            // ディレクトリそのものを書いた場合も配下のファイルに一致させる
+            for pattern in [path.clone(), format!("{}/**", path.trim_end_matches('/'))] {
+                let glob = Glob::new(&pattern).map_err(|e| {
+                    UbiDocError::Config(format!("Invalid context path pattern: {}", e))
+                })?;
+                builder.add(glob);
+            }
+        }
+        let globs = builder
+            .build()
+            .map_err(|e| UbiDocError::Config(format!("Invalid context path pattern: {}", e)))?;
+
+        let namespace_rules = config
+            .namespace_contexts
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (regex, rule.context.clone()))
+                    .map_err(|e| UbiDocError::Config(format!("Invalid namespace pattern: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ContextResolver {
+            globs,
+            namespace_rules,
+            specificities: config
+                .contexts
+                .iter()
+                .map(|mapping| specificity(&mapping.path))
+                .collect(),
+            contexts: config
+                .contexts
+                .iter()
+                .map(|mapping| mapping.context.clone())
+                .collect(),
+        })
+    }
+
+    /// 名前空間から導いたコンテキスト (複数一致した場合は先に書かれたもの)
+    pub fn resolve_namespace(&self, namespace: &str) -> Option<String> {
+        self.namespace_rules.iter().find_map(|(regex, context)| {
+            let captures = regex.captures(namespace)?;
+            let mut resolved = String::new();
+            captures.expand(context, &mut resolved);
+            Some(resolved).filter(|resolved| !resolved.is_empty())
+        })
+    }
+
+    /// ファイルのパスに対応するコンテキスト
+    /// 複数一致した場合はワイルドカードのない階層が多いもの、それも同じなら先に書かれたもの
+    pub fn resolve(&self, file_path: &str) -> Option<&str> {
+        self.globs
+            .matches(normalize(file_path))
+            .into_iter()
+            .map(|index| index / 2)
+            .min_by_key(|&mapping| (Reverse(self.specificities[mapping]), mapping))
+            .map(|mapping| self.contexts[mapping].as_str())
+    }
+
+    /// @context が書かれていない用語に、宣言しているクラス・名前空間・ディレクトリのコンテキストを設定する
+    pub fn apply(&self, list: Vec<Ubiquitous>) -> Vec<Ubiquitous> {
+        let inherited = find_in_parents(&list, |ubiquitous| {
+            ubiquitous
+                .context
+                .clone()
+                .filter(|context| !context.is_empty())
+        });
+        list.into_iter()
+            .zip(inherited)
+            .map(|(ubiquitous, inherited)| {
+                let has_context = ubiquitous
+                    .context
+                    .as_ref()
+                    .is_some_and(|context| !context.is_empty());
+                if has_context {
+                    return ubiquitous;
+                }
+                let context = inherited
+                    .or_else(|| {
+                        ubiquitous
+                            .namespace
+                            .as_deref()
+                            .and_then(|namespace| self.resolve_namespace(namespace))
+                    })
+                    .or_else(|| {
+                        ubiquitous
+                            .file_path
+                            .as_deref()
+                            .and_then(|file_path| self.resolve(file_path))
+                            .map(str::to_string)
+                    });
+                match context {
+                    Some(context) => ubiquitous.set_context(context),
+                    None => ubiquitous,
+                }
+            })
+            .collect()
+    }
+}
+
+/// パターンのうち、ワイルドカードを含まない階層の数 (services/billing/** は 2)
+fn specificity(pattern: &str) -> usize {
+    normalize(pattern)
+        .components()
+        .filter(|component| {
+            !component
+                .as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '[', '{'])
+        })
+        .count()
+}
+
+/// "./" を取り除き、区切り文字を揃える
+fn normalize(path: &str) -> PathBuf {
+    Path::new(&path.replace('\\', "/"))
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ubi_doc_config::{ContextMapping, NamespaceContextRule};
+
+    fn resolver() -> ContextResolver {
+        let config = UbiDocConfig {
+            contexts: vec![
+                ContextMapping {
+                    path: "services/**".to_string(),
+                    context: "Shared".to_string(),
+                },
+                ContextMapping {
+                    path: "services/ordering/**".to_string(),
+                    context: "Ordering".to_string(),
+                },
+                ContextMapping {
+                    path: "./services/billing".to_string(),
+                    context: "Billing".to_string(),
+                },
+                ContextMapping {
+                    path: "services/*/src/**".to_string(),
+                    context: "Sources".to_string(),
+                },
+            ],
+            ..UbiDocConfig::default()
+        };
+        ContextResolver::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_context() {
+        let resolver = resolver();
+
+        // 後に書かれていても、より具体的な対応が優先される
+        assert_eq!(
+            resolver.resolve("services/ordering/domain/Order.php"),
+            Some("Ordering")
+        );
+        assert_eq!(
+            resolver.resolve("./services/billing/Invoice.kt"),
+            Some("Billing")
+        );
+        assert_eq!(
+            resolver.resolve("services/shipping/Parcel.rb"),
+            Some("Shared")
+        );
+        // ワイルドカードのない階層の数が同じなら先に書かれた対応が優先される
+        assert_eq!(
+            resolver.resolve("services/ordering/src/Order.php"),
+            Some("Ordering")
+        );
+        assert_eq!(
+            resolver.resolve("services/shipping/src/Parcel.rb"),
+            Some("Sources")
+        );
+        assert_eq!(resolver.resolve("lib/Util.java"), None);
+    }
+
+    #[test]
+    fn test_apply_context() {
+        let list = vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_file_path("services/ordering/Order.php".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Invoice".to_string())
+                .set_context("Accounting".to_string())
+                .set_file_path("services/billing/Invoice.php".to_string()),
+        ];
+
+        let list = resolver().apply(list);
+
+        assert_eq!(list[0].context, Some("Ordering".to_string()));
+        // @context が書かれていればそちらを優先する
+        assert_eq!(list[1].context, Some("Accounting".to_string()));
+    }
+
+    #[test]
+    fn test_apply_context_from_parent() {
+        let list = vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_qualified_name("App\\Order".to_string())
+                .set_context("Sales".to_string())
+                .set_file_path("services/ordering/Order.php".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Place Order".to_string())
+                .set_parent("App\\Order".to_string())
+                .set_file_path("services/ordering/Order.php".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Cancel Order".to_string())
+                .set_parent("App\\Order".to_string())
+                .set_context("Support".to_string())
+                .set_file_path("services/ordering/Order.php".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Format".to_string())
+                .set_parent("App\\Helpers".to_string())
+                .set_file_path("services/ordering/Helpers.php".to_string()),
+        ];
+
+        let list = resolver().apply(list);
+
+        // クラスに書かれた @context をディレクトリのコンテキストより優先する
+        assert_eq!(list[1].context, Some("Sales".to_string()));
+        assert_eq!(list[2].context, Some("Support".to_string()));
+        // 宣言しているクラスが用語集になければディレクトリのコンテキスト
+        assert_eq!(list[3].context, Some("Ordering".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_namespace() {
+        let config = UbiDocConfig {
+            namespace_contexts: vec![
+                NamespaceContextRule {
+                    pattern: r"^com\.acme\.(\w+)\.".to_string(),
+                    context: "$1".to_string(),
+                },
+                NamespaceContextRule {
+                    pattern: r"^App\\(?P<context>\w+)".to_string(),
+                    context: "${context}Context".to_string(),
+                },
+            ],
+            contexts: vec![ContextMapping {
+                path: "src/**".to_string(),
+                context: "Fallback".to_string(),
+            }],
+            ..UbiDocConfig::default()
+        };
+        let resolver = ContextResolver::from_config(&config).unwrap();
+
+        assert_eq!(
+            resolver.resolve_namespace("com.acme.ordering.domain"),
+            Some("ordering".to_string())
+        );
+        assert_eq!(
+            resolver.resolve_namespace(r"App\Billing\Domain"),
+            Some("BillingContext".to_string())
+        );
+        assert_eq!(resolver.resolve_namespace("org.example"), None);
+
+        let list = resolver.apply(vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_namespace("com.acme.ordering.domain".to_string())
+                .set_file_path("src/Order.java".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Util".to_string())
+                .set_namespace("org.example".to_string())
+                .set_file_path("src/Util.java".to_string()),
+        ]);
+
+        // 名前空間のルールがディレクトリの対応より優先される
+        assert_eq!(list[0].context, Some("ordering".to_string()));
+        assert_eq!(list[1].context, Some("Fallback".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let config = UbiDocConfig {
+            contexts: vec![ContextMapping {
+                path: "services/{ordering".to_string(),
+                context: "Ordering".to_string(),
+            }],
+            ..UbiDocConfig::default()
+        };
+        assert!(ContextResolver::from_config(&config).is_err());
+
+        let config = UbiDocConfig {
+            namespace_contexts: vec![NamespaceContextRule {
+                pattern: "(unclosed".to_string(),
+                context: "$1".to_string(),
+            }],
+            ..UbiDocConfig::default()
+        };
+        assert!(ContextResolver::from_config(&config).is_err());
+    }
+}
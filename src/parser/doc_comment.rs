@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+
+/// Docコメントの記法 (言語ごとにコメント記号の取り除き方が異なる)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// Java / Kotlin / PHP の /** ... */ (および // の行コメント)
+    Block,
+    /// Ruby の # の行コメント
+    Hash,
+}
+
+impl CommentStyle {
+    /// 1行からコメント記号を取り除き、本文だけを返す
+    pub fn strip_markers<'a>(&self, line: &'a str) -> &'a str {
+        let line = line.trim();
+        match self {
+            CommentStyle::Block => {
+                if let Some(rest) = line.strip_prefix("//") {
+                    return rest.trim_start_matches('/').trim();
+                }
+                let line = line
+                    .trim_start_matches("/**")
+                    .trim_start_matches("/*")
+                    .trim_end_matches("*/");
+                let line = line.trim();
+                line.strip_prefix('*').unwrap_or(line).trim()
+            }
+            CommentStyle::Hash => line.trim_start_matches('#').trim(),
+        }
+    }
+}
+
+/// Docコメント中の1つのタグ (例: @description の本文と行番号)
+#[derive(Debug, PartialEq)]
+pub struct DocTag {
+    /// @ を除いたタグ名
+    pub name: String,
+    /// タグの後ろから、次のタグまたはコメントの終わりまでの本文
+    pub value: String,
+    pub line_number: usize,
+}
+
+impl DocTag {
+    /// 本文の1行目 (用語名やコンテキストなど、1行で書く値に使う)
+    pub fn first_line(&self) -> &str {
+        self.value.lines().next().unwrap_or_default()
+    }
+
+    /// "@name:locale" の形のタグであればロケール (例: @ubiquitous:ja の ja) を返す
+    pub fn locale_of(&self, name: &str) -> Option<&str> {
+        let locale = self.name.strip_prefix(name)?.strip_prefix(':')?;
+        let is_locale = !locale.is_empty()
+            && locale
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+        is_locale.then_some(locale)
+    }
+}
+
+/// コメント記号を取り除き、タグごとに分けた Docコメント
+#[derive(Debug, PartialEq)]
+pub struct DocComment {
+    /// 最初のタグより前にある、タグのない先頭の段落
+    pub leading_paragraph: Option<String>,
+    pub tags: Vec<DocTag>,
+}
+
+impl DocComment {
+    /// first_line_number: コメントの1行目の行番号
+    pub fn parse(comment: &str, style: CommentStyle, first_line_number: usize) -> Self {
+        let mut leading_lines: Vec<&str> = Vec::new();
+        let mut leading_done = false;
+        let mut tags: Vec<DocTag> = Vec::new();
+        let mut value_lines: Vec<&str> = Vec::new();
+
+        for (line_index, line) in comment.trim().lines().enumerate() {
+            let line = style.strip_markers(line);
+
+            if let Some((name, value)) = parse_tag_line(line) {
+                if let Some(tag) = tags.last_mut() {
+                    tag.value = join_lines(&value_lines);
+                }
+                value_lines = vec![value];
+                tags.push(DocTag {
+                    name: name.to_string(),
+                    value: String::new(),
+                    line_number: first_line_number + line_index,
+                });
+            } else if !tags.is_empty() {
+                // タグの続きの行
+                value_lines.push(line);
+            } else if line.is_empty() {
+                // 先頭の段落は最初の空行までとする
+                leading_done = !leading_lines.is_empty();
+            } else if !leading_done {
+                leading_lines.push(line);
+            }
+        }
+        if let Some(tag) = tags.last_mut() {
+            tag.value = join_lines(&value_lines);
+        }
+
+        let leading_paragraph = join_lines(&leading_lines);
+        DocComment {
+            leading_paragraph: (!leading_paragraph.is_empty()).then_some(leading_paragraph),
+            tags,
+        }
+    }
+
+    /// 別名のタグを対応するタグ名に置き換える (例: @用語 → @ubiquitous)
+    /// "@name:locale" の形のタグはロケールを残して置き換える
+    pub fn resolve_aliases(&mut self, aliases: &BTreeMap<String, String>) {
+        if aliases.is_empty() {
+            return;
+        }
+        for tag in self.tags.iter_mut() {
+            let (name, locale) = match tag.name.split_once(':') {
+                Some((name, locale)) => (name, Some(locale)),
+                None => (tag.name.as_str(), None),
+            };
+            if let Some(resolved) = aliases.get(name) {
+                tag.name = match locale {
+                    Some(locale) => format!("{}:{}", resolved, locale),
+                    None => resolved.clone(),
+                };
+            }
+        }
+    }
+
+    /// 指定した名前のタグごとにタグを区切る
+    /// 戻り値: (最初の区切りより前のタグ, 区切りのタグから始まるタグのまとまり)
+    pub fn split_at_tag(&self, name: &str) -> (Vec<&DocTag>, Vec<Vec<&DocTag>>) {
+        let mut preamble = Vec::new();
+        let mut blocks: Vec<Vec<&DocTag>> = Vec::new();
+        for tag in self.tags.iter() {
+            match blocks.last_mut() {
+                _ if tag.name == name => blocks.push(vec![tag]),
+                Some(block) => block.push(tag),
+                None => preamble.push(tag),
+            }
+        }
+        (preamble, blocks)
+    }
+}
+
+/// "@name value" の行であれば (name, value) を返す
+fn parse_tag_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('@')?;
+    let (name, value) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, value.trim()))
+}
+
+/// 行を改行でつなぎ、前後の空行を取り除く
+fn join_lines(lines: &[&str]) -> String {
+    lines.join("\n").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_of() {
+        let tag = |name: &str| DocTag {
+            name: name.to_string(),
+            value: String::new(),
+            line_number: 1,
+        };
+
+        assert_eq!(tag("ubiquitous:ja").locale_of("ubiquitous"), Some("ja"));
+        assert_eq!(
+            tag("description:pt-BR").locale_of("description"),
+            Some("pt-BR")
+        );
+        assert_eq!(tag("ubiquitous").locale_of("ubiquitous"), None);
+        assert_eq!(tag("ubiquitous:").locale_of("ubiquitous"), None);
+        assert_eq!(tag("ubiquitousx:ja").locale_of("ubiquitous"), None);
+    }
+
+    #[test]
+    fn test_strip_markers() {
+        assert_eq!(CommentStyle::Block.strip_markers("/**"), "");
+        assert_eq!(
+            CommentStyle::Block.strip_markers("  * @ubiquitous 注文"),
+            "@ubiquitous 注文"
+        );
+        assert_eq!(CommentStyle::Block.strip_markers(" */"), "");
+        assert_eq!(CommentStyle::Block.strip_markers("/** 注文 */"), "注文");
+        assert_eq!(CommentStyle::Block.strip_markers("/// 注文"), "注文");
+        assert_eq!(CommentStyle::Hash.strip_markers("  ## 注文"), "注文");
+    }
+
+    #[test]
+    fn test_parse_multi_line_tag() {
+        let comment = r#"/**
+ * 顧客からの注文を表す。
+ * 明細を持つ。
+ *
+ * 詳細な説明は省略する。
+ * @ubiquitous 注文
+ * @description 顧客が商品を購入する依頼。
+ *   明細と配送先を持つ。
+ *
+ *   キャンセルできる。
+ * @context 販売
+ */"#;
+
+        let doc = DocComment::parse(comment, CommentStyle::Block, 10);
+
+        assert_eq!(
+            doc.leading_paragraph.as_deref(),
+            Some("顧客からの注文を表す。\n明細を持つ。")
+        );
+        assert_eq!(doc.tags.len(), 3);
+        assert_eq!(doc.tags[0].name, "ubiquitous");
+        assert_eq!(doc.tags[0].value, "注文");
+        assert_eq!(doc.tags[0].line_number, 15);
+        assert_eq!(
+            doc.tags[1].value,
+            "顧客が商品を購入する依頼。\n明細と配送先を持つ。\n\nキャンセルできる。"
+        );
+        assert_eq!(doc.tags[2].first_line(), "販売");
+    }
+
+    #[test]
+    fn test_split_at_tag() {
+        let comment = "/**\n * @context 販売\n * @ubiquitous 注文\n * @description A\n * @ubiquitous 受注\n */";
+
+        let doc = DocComment::parse(comment, CommentStyle::Block, 1);
+        let (preamble, blocks) = doc.split_at_tag("ubiquitous");
+
+        assert_eq!(preamble.len(), 1);
+        assert_eq!(preamble[0].name, "context");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].len(), 2);
+        assert_eq!(blocks[0][1].value, "A");
+        assert_eq!(blocks[1][0].value, "受注");
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        let comment = "/**\n * @用語 注文\n * @用語:en Order\n * @bc 販売\n * @see 請求\n */";
+        let aliases = BTreeMap::from([
+            ("用語".to_string(), "ubiquitous".to_string()),
+            ("bc".to_string(), "context".to_string()),
+        ]);
+
+        let mut doc = DocComment::parse(comment, CommentStyle::Block, 1);
+        doc.resolve_aliases(&aliases);
+
+        let names: Vec<_> = doc.tags.iter().map(|tag| tag.name.as_str()).collect();
+        assert_eq!(names, vec!["ubiquitous", "ubiquitous:en", "context", "see"]);
+    }
+
+    #[test]
+    fn test_parse_ruby_comment() {
+        let comment = "# 注文\n# @ubiquitous Order\n#   continued";
+
+        let doc = DocComment::parse(comment, CommentStyle::Hash, 1);
+
+        assert_eq!(doc.leading_paragraph.as_deref(), Some("注文"));
+        assert_eq!(doc.tags[0].value, "Order\ncontinued");
+        assert_eq!(doc.tags[0].first_line(), "Order");
+    }
+}
@@ -0,0 +1,700 @@
+use crate::config::ubi_doc_config::TagConfig;
+use crate::parser::class_doc::DeclarationKind;
+use crate::parser::doc_comment::{CommentStyle, DocComment};
+use crate::parser::stereotype::Stereotype;
+use crate::parser::ubiquitous::Ubiquitous;
+
+pub struct ExtractUbiquitousParam {
+    pub class_name: String,
+    pub kind: DeclarationKind,
+    pub qualified_name: String,
+    /// メソッド・列挙子などを宣言しているクラスの完全修飾名
+    pub parent: Option<String>,
+    pub doc_comment: String,
+    pub comment_style: CommentStyle,
+    pub file_path: String,
+    pub line_number: usize,
+    pub namespace: Option<String>,
+    /// クラスの中で使われている型名
+    pub type_references: Vec<String>,
+}
+
+pub fn extract_ubiquitous(
+    class_docs: Vec<ExtractUbiquitousParam>,
+    tags: &TagConfig,
+) -> Vec<Ubiquitous> {
+    class_docs
+        .into_iter()
+        .flat_map(|class_doc| get_ubiquitous(class_doc, tags))
+        .filter(|u| !u.is_all_none())
+        .collect()
+}
+
+/// 1つの Docコメントから、@ubiquitous ごとに用語を取り出す
+/// @ubiquitous より前に書かれた @context などは、すべての用語に共通の値とする
+fn get_ubiquitous(class_doc: ExtractUbiquitousParam, tags: &TagConfig) -> Vec<Ubiquitous> {
+    let mut doc = DocComment::parse(
+        &class_doc.doc_comment,
+        class_doc.comment_style,
+        class_doc.line_number,
+    );
+    doc.resolve_aliases(&tags.aliases);
+    let (preamble, blocks) = doc.split_at_tag(&tags.ubiquitous);
+
+    blocks
+        .iter()
+        .map(|block| {
+            // 用語のまとまりの中のタグを優先し、なければ共通のタグを使う
+            let find_tag = |name: &str| {
+                block
+                    .iter()
+                    .chain(preamble.iter())
+                    .find(|tag| tag.name == name)
+                    .copied()
+            };
+
+            // @ubiquitous (line_number は @ubiquitous の行にする)
+            let ubiquitous_tag = block[0];
+            let mut result = Ubiquitous::new()
+                .set_class_name(class_doc.class_name.clone())
+                .set_qualified_name(class_doc.qualified_name.clone())
+                .set_kind(class_doc.kind)
+                .set_ubiquitous(ubiquitous_tag.first_line().to_string())
+                .set_line_number(ubiquitous_tag.line_number);
+
+            // @context
+            if let Some(context_tag) = find_tag(&tags.context) {
+                result = result.set_context(context_tag.first_line().to_string());
+            }
+
+            // @description (複数行可)。なければタグのない先頭の段落を説明とする
+            let description = find_tag(&tags.description)
+                .map(|description_tag| description_tag.value.clone())
+                .filter(|description| !description.is_empty())
+                .or_else(|| doc.leading_paragraph.clone());
+            if let Some(description) = description {
+                result = result.set_description(description);
+            }
+
+            // @alias / @synonym (カンマ区切り・複数指定可)
+            for alias_tag in block
+                .iter()
+                .chain(preamble.iter())
+                .filter(|tag| tag.name == tags.alias || tag.name == tags.synonym)
+            {
+                for alias in alias_tag.first_line().split(',') {
+                    result = result.add_alias(alias.trim().to_string());
+                }
+            }
+
+            // @see / @related (カンマ区切り・複数指定可)
+            for related_tag in block
+                .iter()
+                .chain(preamble.iter())
+                .filter(|tag| tag.name == tags.see || tag.name == tags.related)
+            {
+                for reference in related_tag.first_line().split(',') {
+                    result = result.add_related(reference.trim().to_string());
+                }
+            }
+
+            // @deprecatedTerm (値は置き換え先の用語。空でもよい)
+            if let Some(deprecated_tag) = find_tag(&tags.deprecated) {
+                result = result.set_deprecated(deprecated_tag.first_line().to_string());
+            }
+
+            // @ubiquitous:ja / @description:ja などの翻訳 (用語のまとまりの中のタグを優先する)
+            for tag in block.iter().chain(preamble.iter()) {
+                if let Some(locale) = tag.locale_of(&tags.ubiquitous) {
+                    let translated = result.translations.get(locale);
+                    if translated.is_none_or(|translation| translation.ubiquitous.is_none()) {
+                        result = result.set_translated_ubiquitous(
+                            locale.to_string(),
+                            tag.first_line().to_string(),
+                        );
+                    }
+                } else if let Some(locale) = tag.locale_of(&tags.description) {
+                    let translated = result.translations.get(locale);
+                    if translated.is_none_or(|translation| translation.description.is_none())
+                        && !tag.value.is_empty()
+                    {
+                        result = result
+                            .set_translated_description(locale.to_string(), tag.value.clone());
+                    }
+                }
+            }
+
+            // @invariant / @rule と @termExample (複数指定可・複数行可)
+            // 用語のまとまりより前に書かれた共通のタグも含め、書かれた順に並べる
+            for tag in preamble.iter().chain(block.iter()) {
+                if tag.name == tags.invariant || tag.name == tags.rule {
+                    result = result.add_rule(tag.value.clone());
+                } else if tag.name == tags.example {
+                    result = result.add_example(tag.value.clone());
+                }
+            }
+
+            // @stereotype、または @aggregate / @entity などの専用タグ
+            // 集約ルート以外の専用タグの値は、所属する集約ルートの用語とする
+            if let Some(stereotype) = find_tag(&tags.stereotype)
+                .and_then(|stereotype_tag| Stereotype::from_name(stereotype_tag.first_line()))
+            {
+                result = result.set_stereotype(stereotype);
+            }
+            let stereotype_tags = [
+                (&tags.aggregate, Stereotype::Aggregate),
+                (&tags.entity, Stereotype::Entity),
+                (&tags.value_object, Stereotype::ValueObject),
+                (&tags.event, Stereotype::DomainEvent),
+                (&tags.service, Stereotype::Service),
+                (&tags.policy, Stereotype::Policy),
+            ];
+            for (tag_name, stereotype) in stereotype_tags {
+                let Some(stereotype_tag) = find_tag(tag_name) else {
+                    continue;
+                };
+                if result.stereotype.is_none() {
+                    result = result.set_stereotype(stereotype);
+                }
+                let aggregate = stereotype_tag.first_line().trim();
+                if stereotype != Stereotype::Aggregate && !aggregate.is_empty() {
+                    result = result.set_aggregate(aggregate.to_string());
+                }
+            }
+
+            // @owner (カンマ区切り)
+            if let Some(owner_tag) = find_tag(&tags.owner) {
+                for team in owner_tag.first_line().split(',') {
+                    result = result.add_team(team.trim().to_string());
+                }
+            }
+
+            // 設定で追加したタグ (@jira / @status など。複数行可)
+            for name in tags.extra.iter() {
+                if let Some(extra_tag) = find_tag(name) {
+                    result = result.set_extra_field(name.clone(), extra_tag.value.clone());
+                }
+            }
+
+            result = result.set_file_path(class_doc.file_path.clone());
+            if let Some(parent) = &class_doc.parent {
+                result = result.set_parent(parent.clone());
+            }
+            if let Some(namespace) = &class_doc.namespace {
+                result = result.set_namespace(namespace.clone());
+            }
+            result.set_type_references(class_doc.type_references.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_extract_ubiquitous_empty() {
+        let doc_comments = vec![];
+
+        let result = extract_ubiquitous(doc_comments, &TagConfig::default());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_single() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "class_name".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "class_name".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+    * @ubiquitous ubiquitous_lang
+    */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "tmp/saple.php".to_string(),
+            line_number: 2,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0],
+            Ubiquitous::new()
+                .set_class_name("class_name".to_string())
+                .set_qualified_name("class_name".to_string())
+                .set_kind(DeclarationKind::Class)
+                .set_ubiquitous("ubiquitous_lang".to_string())
+                .set_file_path("tmp/saple.php".to_string())
+                .set_line_number(3)
+        );
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_multi_fields() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "class_name".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "class_name".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+    *
+    * @ubiquitous ubiquitous_lang
+    * @context context_example
+    * @description description_text
+    */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "tmp/saple.php".to_string(),
+            line_number: 3,
+            namespace: Some("App\\Sales".to_string()),
+            type_references: Vec::new(),
+        }];
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+        assert_eq!(result.len(), 1);
+
+        let expected = Ubiquitous::new()
+            .set_class_name("class_name".to_string())
+            .set_qualified_name("class_name".to_string())
+            .set_kind(DeclarationKind::Class)
+            .set_ubiquitous("ubiquitous_lang".to_string())
+            .set_context("context_example".to_string())
+            .set_description("description_text".to_string())
+            .set_file_path("tmp/saple.php".to_string())
+            .set_namespace("App\\Sales".to_string())
+            .set_line_number(5);
+        assert_eq!(result[0], expected);
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_custom_tags() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Enum,
+            qualified_name: "com.acme.Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+    * @term Order
+    * @bc Sales
+    * @summary purchase order
+    */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "tmp/sample.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+        let tags = TagConfig {
+            ubiquitous: "term".to_string(),
+            context: "bc".to_string(),
+            description: "summary".to_string(),
+            ..TagConfig::default()
+        };
+
+        let result = extract_ubiquitous(class_docs, &tags);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].ubiquitous, "Order");
+        assert_eq!(result[0].qualified_name, Some("com.acme.Order".to_string()));
+        assert_eq!(result[0].kind, Some(DeclarationKind::Enum));
+        assert_eq!(result[0].context, Some("Sales".to_string()));
+        assert_eq!(result[0].description, Some("purchase order".to_string()));
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_aliases_and_extra_tags() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+    * @チケット SALES-12
+    * @用語 注文
+    * @文脈 販売
+    * @ubiquitous:en Order
+    * @status draft
+    *   needs review
+    * @用語 受注
+    * @status approved
+    */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.php".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+        let tags = TagConfig {
+            aliases: BTreeMap::from([
+                ("用語".to_string(), "ubiquitous".to_string()),
+                ("文脈".to_string(), "context".to_string()),
+                ("チケット".to_string(), "jira".to_string()),
+            ]),
+            extra: vec!["jira".to_string(), "status".to_string()],
+            ..TagConfig::default()
+        };
+
+        let result = extract_ubiquitous(class_docs, &tags);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].ubiquitous, "注文");
+        assert_eq!(result[0].context.as_deref(), Some("販売"));
+        assert_eq!(
+            result[0].translations["en"].ubiquitous.as_deref(),
+            Some("Order")
+        );
+        assert_eq!(
+            result[0].extra_fields,
+            BTreeMap::from([
+                ("jira".to_string(), "SALES-12".to_string()),
+                ("status".to_string(), "draft\nneeds review".to_string()),
+            ])
+        );
+        // 用語のまとまりの中のタグを優先し、なければ共通のタグを使う
+        assert_eq!(result[1].ubiquitous, "受注");
+        assert_eq!(result[1].extra_fields["status"], "approved");
+        assert_eq!(result[1].extra_fields["jira"], "SALES-12");
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_multi_line_description() {
+        let class_docs = vec![
+            ExtractUbiquitousParam {
+                class_name: "Order".to_string(),
+                kind: DeclarationKind::Class,
+                qualified_name: "Order".to_string(),
+                parent: None,
+                doc_comment: r#"/**
+ * @ubiquitous 注文
+ * @description 顧客が商品を購入する依頼。
+ *   明細と配送先を持つ。
+ * @see Invoice
+ */"#
+                .to_string(),
+                comment_style: CommentStyle::Block,
+                file_path: "Order.java".to_string(),
+                line_number: 1,
+                namespace: None,
+                type_references: Vec::new(),
+            },
+            ExtractUbiquitousParam {
+                class_name: "Invoice".to_string(),
+                kind: DeclarationKind::Class,
+                qualified_name: "Invoice".to_string(),
+                parent: None,
+                doc_comment: "# 請求の内容を表す。\n# 支払期限を持つ。\n#\n# @ubiquitous 請求書"
+                    .to_string(),
+                comment_style: CommentStyle::Hash,
+                file_path: "invoice.rb".to_string(),
+                line_number: 1,
+                namespace: None,
+                type_references: Vec::new(),
+            },
+        ];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result.len(), 2);
+        // 次のタグの前までが説明になる
+        assert_eq!(
+            result[0].description.as_deref(),
+            Some("顧客が商品を購入する依頼。\n明細と配送先を持つ。")
+        );
+        // @description がなければ先頭の段落を説明とする
+        assert_eq!(result[1].ubiquitous, "請求書");
+        assert_eq!(result[1].line_number, Some(4));
+        assert_eq!(
+            result[1].description.as_deref(),
+            Some("請求の内容を表す。\n支払期限を持つ。")
+        );
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_multiple_entries() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @description 共通の説明
+ * @ubiquitous 注文
+ * @context 販売
+ * @ubiquitous 受注
+ * @context 配送
+ * @description 配送側から見た注文
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].ubiquitous, "注文");
+        assert_eq!(result[0].context.as_deref(), Some("販売"));
+        assert_eq!(result[0].description.as_deref(), Some("共通の説明"));
+        assert_eq!(result[0].line_number, Some(3));
+        assert_eq!(result[1].ubiquitous, "受注");
+        assert_eq!(result[1].context.as_deref(), Some("配送"));
+        assert_eq!(result[1].description.as_deref(), Some("配送側から見た注文"));
+        assert_eq!(result[1].line_number, Some(5));
+        assert_eq!(result[1].class_name.as_deref(), Some("Order"));
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_aliases_and_deprecated() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Purchase".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Purchase".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @ubiquitous 購入
+ * @alias 買い物, 購買
+ * @synonym 購買
+ * @synonym 仕入れ
+ * @deprecatedTerm 注文
+ * @deprecated use Order instead
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Purchase.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result.len(), 1);
+        // 重複した別名は1つにまとめる
+        assert_eq!(result[0].aliases, vec!["買い物", "購買", "仕入れ"]);
+        // Javadoc の @deprecated は用語の置き換え先として扱わない
+        assert_eq!(result[0].deprecated.as_deref(), Some("注文"));
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_related() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @ubiquitous 注文
+ * @description {@link 注文明細} を1つ以上持つ。
+ * @see 注文明細
+ * @related Billing:請求書, 顧客
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(
+            result[0].related,
+            vec!["注文明細", "Billing:請求書", "顧客"]
+        );
+        assert_eq!(
+            result[0].description.as_deref(),
+            Some("{@link 注文明細} を1つ以上持つ。")
+        );
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_related_in_preamble() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @see 顧客
+ * @ubiquitous 注文
+ * @context Sales
+ * @ubiquitous Order
+ * @context Shipping
+ * @related 出荷
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        // 最初の @ubiquitous より前の @see はすべての用語に付く
+        assert_eq!(result[0].related, vec!["顧客"]);
+        assert_eq!(result[1].related, vec!["出荷", "顧客"]);
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_stereotype() {
+        let param = |class_name: &str, doc_comment: &str| ExtractUbiquitousParam {
+            class_name: class_name.to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: class_name.to_string(),
+            parent: None,
+            doc_comment: doc_comment.to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        };
+        let class_docs = vec![
+            param("Order", "/**\n * @ubiquitous 注文\n * @aggregate\n */"),
+            param(
+                "OrderLine",
+                "/**\n * @ubiquitous 注文明細\n * @entity 注文\n */",
+            ),
+            param(
+                "Money",
+                "/**\n * @ubiquitous 金額\n * @stereotype value object\n */",
+            ),
+            param(
+                "Placed",
+                "/**\n * @ubiquitous 注文確定\n * @stereotype unknown\n */",
+            ),
+        ];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result[0].stereotype, Some(Stereotype::Aggregate));
+        assert_eq!(result[0].aggregate, None);
+        assert_eq!(result[1].stereotype, Some(Stereotype::Entity));
+        assert_eq!(result[1].aggregate.as_deref(), Some("注文"));
+        assert_eq!(result[2].stereotype, Some(Stereotype::ValueObject));
+        assert_eq!(result[3].stereotype, None);
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_rules_and_examples() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @rule 注文は取り消せる。
+ * @ubiquitous 注文
+ * @invariant 支払いが確定するまで発送できない。
+ * @rule 明細は1件以上必要。
+ *       上限は100件。
+ * @termExample 2024-04-01 に顧客 A が商品 X を2個注文する。
+ * @example Order.php
+ * @rule
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        // 書かれた順に並べ、空のタグは無視する
+        assert_eq!(
+            result[0].rules,
+            vec![
+                "注文は取り消せる。",
+                "支払いが確定するまで発送できない。",
+                "明細は1件以上必要。\n上限は100件。"
+            ]
+        );
+        // PHPDoc の @example (サンプルファイルの指定) は使用例として扱わない
+        assert_eq!(
+            result[0].examples,
+            vec!["2024-04-01 に顧客 A が商品 X を2個注文する。"]
+        );
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_translations() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @description:ja 顧客からの注文。
+ * @ubiquitous Order
+ * @ubiquitous:ja 注文
+ * @description A purchase by a customer.
+ * @description:ja 顧客による購入。
+ *                 複数の明細を持つ。
+ * @ubiquitous:en-GB Purchase order
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].ubiquitous, "Order");
+        let ja = &result[0].translations["ja"];
+        assert_eq!(ja.ubiquitous.as_deref(), Some("注文"));
+        // 用語のまとまりの中の翻訳を優先する
+        assert_eq!(
+            ja.description.as_deref(),
+            Some("顧客による購入。\n複数の明細を持つ。")
+        );
+        let en_gb = &result[0].translations["en-GB"];
+        assert_eq!(en_gb.ubiquitous.as_deref(), Some("Purchase order"));
+        assert_eq!(en_gb.description, None);
+    }
+
+    #[test]
+    fn test_extract_ubiquitous_owner() {
+        let class_docs = vec![ExtractUbiquitousParam {
+            class_name: "Order".to_string(),
+            kind: DeclarationKind::Class,
+            qualified_name: "Order".to_string(),
+            parent: None,
+            doc_comment: r#"/**
+ * @owner @acme/sales, @acme/billing
+ * @ubiquitous Order
+ * @ubiquitous Invoice
+ * @owner @acme/billing
+ */"#
+            .to_string(),
+            comment_style: CommentStyle::Block,
+            file_path: "Order.java".to_string(),
+            line_number: 1,
+            namespace: None,
+            type_references: Vec::new(),
+        }];
+
+        let result = extract_ubiquitous(class_docs, &TagConfig::default());
+
+        assert_eq!(result[0].teams, vec!["@acme/sales", "@acme/billing"]);
+        assert_eq!(result[1].teams, vec!["@acme/billing"]);
+        // --owner は先頭の @ と大文字・小文字の違いを無視して比べる
+        assert!(result[0].is_owned_by("ACME/sales"));
+        assert!(!result[1].is_owned_by("@acme/sales"));
+    }
+}
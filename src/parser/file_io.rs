@@ -1 +1,4 @@
+pub mod decode_source;
+pub mod path_filter;
 pub mod read_sources_from_dir;
+pub mod walk_options;
@@ -0,0 +1,75 @@
+use encoding_rs::{Encoding, UTF_8};
+
+/// バイナリ判定のために先頭から調べるバイト数
+const BINARY_CHECK_LEN: usize = 8000;
+
+/// ソースファイルを文字列に変換できなかった理由
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// NUL 文字を含むためバイナリファイルとみなした
+    Binary,
+    /// UTF-8 / 指定されたどのエンコーディングでもデコードできなかった
+    UnknownEncoding,
+}
+
+/// バイト列をソースコードの文字列に変換する
+/// BOM があればそのエンコーディング、なければ UTF-8 → encodings の順に試す
+pub fn decode_source(bytes: &[u8], encodings: &[&'static Encoding]) -> Result<String, DecodeError> {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        return encoding
+            .decode_without_bom_handling_and_without_replacement(&bytes[bom_len..])
+            .map(|code| code.into_owned())
+            .ok_or(DecodeError::UnknownEncoding);
+    }
+
+    let head = &bytes[..bytes.len().min(BINARY_CHECK_LEN)];
+    if head.contains(&0) {
+        return Err(DecodeError::Binary);
+    }
+
+    std::iter::once(UTF_8)
+        .chain(encodings.iter().copied())
+        .find_map(|encoding| encoding.decode_without_bom_handling_and_without_replacement(bytes))
+        .map(|code| code.into_owned())
+        .ok_or(DecodeError::UnknownEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{EUC_JP, SHIFT_JIS};
+
+    #[test]
+    fn test_decode_source_utf8() {
+        assert_eq!(
+            decode_source("注文".as_bytes(), &[]),
+            Ok("注文".to_string())
+        );
+        // BOM 付き UTF-8 は BOM を取り除く
+        assert_eq!(
+            decode_source(b"\xEF\xBB\xBFclass Order", &[]),
+            Ok("class Order".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_source_fallback_encodings() {
+        let (shift_jis, _, _) = SHIFT_JIS.encode("// 注文クラス");
+
+        // フォールバックが指定されていなければデコードできない
+        assert_eq!(
+            decode_source(&shift_jis, &[]),
+            Err(DecodeError::UnknownEncoding)
+        );
+        assert_eq!(
+            decode_source(&shift_jis, &[EUC_JP, SHIFT_JIS]),
+            Ok("// 注文クラス".to_string())
+        );
+    }
+
+    #[test]
+    fn test_decode_source_binary() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+        assert_eq!(decode_source(png, &[SHIFT_JIS]), Err(DecodeError::Binary));
+    }
+}
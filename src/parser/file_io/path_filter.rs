@@ -0,0 +1,85 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// include / exclude の glob によるファイルの絞り込み
+/// パスは走査のルートディレクトリからの相対パスで判定する
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl PathFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, globset::Error> {
+        let include = if include.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(include)?)
+        };
+        Ok(PathFilter {
+            include,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    /// ファイルが走査対象かどうか
+    pub fn is_match(&self, relative_path: &Path) -> bool {
+        let included = self
+            .include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative_path));
+        included && !self.is_excluded(relative_path)
+    }
+
+    /// ディレクトリ・ファイルが除外対象かどうか
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        self.exclude.is_match(relative_path)
+    }
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        PathFilter {
+            include: None,
+            exclude: GlobSet::empty(),
+        }
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_filter_default() {
+        let filter = PathFilter::default();
+        assert!(filter.is_match(Path::new("src/Order.php")));
+    }
+
+    #[test]
+    fn test_path_filter_include_exclude() {
+        let filter = PathFilter::new(
+            &["src/**".to_string()],
+            &["**/vendor/**".to_string(), "**/*Test.php".to_string()],
+        )
+        .unwrap();
+
+        assert!(filter.is_match(Path::new("src/Order.php")));
+        assert!(!filter.is_match(Path::new("lib/Order.php")));
+        assert!(!filter.is_match(Path::new("src/vendor/acme/Order.php")));
+        assert!(!filter.is_match(Path::new("src/OrderTest.php")));
+    }
+
+    #[test]
+    fn test_path_filter_invalid_glob() {
+        assert!(PathFilter::new(&["src/[".to_string()], &[]).is_err());
+    }
+}
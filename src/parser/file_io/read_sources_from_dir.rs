@@ -1,7 +1,16 @@
-use std::error::Error;
+use ignore::WalkBuilder;
 use std::fs;
 use std::path::Path;
-use walkdir::WalkDir;
+
+use crate::diagnostics::{
+    diagnostic::Severity, diagnostic_collector::DiagnosticCollector, ubi_doc_error::UbiDocError,
+};
+use crate::parser::get_ubiquitous_list::FileType;
+
+use super::{
+    decode_source::{decode_source, DecodeError},
+    walk_options::WalkOptions,
+};
 
 /// ファイルのコードと拡張子を保持する構造体
 #[derive(Debug)]
@@ -12,31 +21,100 @@ pub struct CodeFile {
 }
 
 /// ディレクトリ配下のソースコードを再帰的に走査して、CodeFile のリストを返す
-pub fn read_sources_from_dir(dir_path: &Path) -> Result<Vec<CodeFile>, Box<dyn Error>> {
-    let mut result = Vec::new();
-
-    for entry in WalkDir::new(dir_path) {
-        let entry = entry?;
-        if entry.file_type().is_file() {
-            let path = entry.path();
-            if let Some(ext) = path.extension() {
-                let ext_str = ext.to_string_lossy().to_string();
-                let code = fs::read_to_string(path)?;
-                result.push(CodeFile {
-                    code,
-                    extension: ext_str,
-                    file_path: path.to_string_lossy().to_string(),
-                });
+/// - 対応言語の拡張子を持つファイルのみ読み込む
+/// - exclude に一致するディレクトリ・ファイル、include に一致しないファイルは読み込まない
+/// - respect_ignore_files が有効な場合は .gitignore / .ignore に書かれたファイルと隠しファイルを読み込まない
+/// - バイナリやデコードできないファイルはスキップし、diagnostics に記録する
+pub fn read_sources_from_dir(
+    dir_path: &Path,
+    options: &WalkOptions,
+    diagnostics: &DiagnosticCollector,
+) -> Result<Vec<CodeFile>, UbiDocError> {
+    if !dir_path.is_dir() {
+        return Err(UbiDocError::InputNotFound(dir_path.to_path_buf()));
+    }
+
+    let mut code_files = Vec::new();
+
+    let root = dir_path.to_path_buf();
+    let filter = options.filter.clone();
+    let walker = WalkBuilder::new(dir_path)
+        .standard_filters(options.respect_ignore_files)
+        // .git ディレクトリがなくても .gitignore を有効にする
+        .require_git(false)
+        .follow_links(options.follow_symlinks)
+        .filter_entry(move |entry| {
+            entry.depth() == 0 || !filter.is_excluded(relative_path(&root, entry.path()))
+        })
+        .build();
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                diagnostics.report(Severity::Warning, UbiDocError::Walk(e.to_string()));
+                continue;
+            }
+        };
+        let is_file = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file());
+        if !is_file
+            || !options
+                .filter
+                .is_match(relative_path(dir_path, entry.path()))
+        {
+            continue;
+        }
+        diagnostics.file_scanned();
+
+        let path = entry.path();
+        let file_path = path.to_string_lossy().to_string();
+        let ext_str = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // 対応していない拡張子のファイルは読み込まない
+        if FileType::from_extension(&ext_str).language_name().is_none() {
+            diagnostics.file_skipped(Severity::Info, UbiDocError::UnsupportedFile { file_path });
+            continue;
+        }
+
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(source) => {
+                diagnostics.file_failed(UbiDocError::Read { file_path, source });
+                continue;
+            }
+        };
+        match decode_source(&bytes, &options.encodings) {
+            Ok(code) => code_files.push(CodeFile {
+                code,
+                extension: ext_str,
+                file_path,
+            }),
+            Err(DecodeError::Binary) => {
+                diagnostics.file_skipped(Severity::Warning, UbiDocError::Binary { file_path })
+            }
+            Err(DecodeError::UnknownEncoding) => {
+                diagnostics.file_failed(UbiDocError::Decode { file_path })
             }
         }
     }
 
-    Ok(result)
+    Ok(code_files)
+}
+
+/// 走査のルートディレクトリからの相対パス
+fn relative_path<'a>(root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::file_io::path_filter::PathFilter;
+    use std::error::Error;
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
@@ -46,19 +124,23 @@ mod tests {
         // 一時ディレクトリを作成
         let dir = tempdir()?;
 
-        // ダミーファイルを1つ作成 (.rs ファイル)
-        let file_path = dir.path().join("hello.rs");
+        // ダミーファイルを1つ作成 (.php ファイル)
+        let file_path = dir.path().join("hello.php");
         let mut file = File::create(&file_path)?;
-        writeln!(file, "fn main() {{ println!(\"Hello, world!\"); }}")?;
+        writeln!(file, "<?php echo \"Hello, world!\";")?;
 
         // テスト対象関数を呼び出し
-        let results = read_sources_from_dir(dir.path())?;
+        let results = read_sources_from_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        )?;
         // 期待値: ファイルは1つだけのはず
         assert_eq!(results.len(), 1);
 
         let code_file = &results[0];
-        assert_eq!(code_file.extension, "rs");
-        assert!(code_file.code.contains("println!(\"Hello, world!\");"));
+        assert_eq!(code_file.extension, "php");
+        assert!(code_file.code.contains("echo \"Hello, world!\";"));
 
         Ok(())
     }
@@ -68,53 +150,78 @@ mod tests {
         // 空の一時ディレクトリを作成
         let dir = tempdir()?;
         // テスト対象関数を呼び出し
-        let results = read_sources_from_dir(dir.path())?;
+        let results = read_sources_from_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        )?;
         // 期待値: ファイルがないので結果は空のはず
         assert_eq!(results.len(), 0);
 
         Ok(())
     }
 
+    #[test]
+    fn test_read_sources_missing_dir() {
+        let dir = tempdir().unwrap();
+        let result = read_sources_from_dir(
+            &dir.path().join("missing"),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        );
+        assert!(matches!(result, Err(UbiDocError::InputNotFound(_))));
+    }
+
     #[test]
     fn test_read_sources_multiple_extensions() -> Result<(), Box<dyn Error>> {
         // 一時ディレクトリを作成
         let dir = tempdir()?;
 
-        // hello.rs
-        let file_path_rs = dir.path().join("hello.rs");
-        let mut file_rs = File::create(&file_path_rs)?;
-        writeln!(file_rs, "fn main() {{ println!(\"Hello, Rust!\"); }}")?;
+        // Hello.kt
+        let file_path_kt = dir.path().join("Hello.kt");
+        let mut file_kt = File::create(&file_path_kt)?;
+        writeln!(file_kt, "fun main() {{ println(\"Hello, Kotlin!\") }}")?;
 
-        // hello.txt
-        let file_path_txt = dir.path().join("hello.txt");
-        let mut file_txt = File::create(&file_path_txt)?;
+        // hello.rb
+        let file_path_rb = dir.path().join("hello.rb");
+        let mut file_rb = File::create(&file_path_rb)?;
+        writeln!(file_rb, "puts 'Hello, Ruby!'")?;
+
+        // 対応していない拡張子 (hello.txt / hello.rs)
+        let mut file_txt = File::create(dir.path().join("hello.txt"))?;
         writeln!(file_txt, "Hello, Text!")?;
+        let mut file_rs = File::create(dir.path().join("hello.rs"))?;
+        writeln!(file_rs, "fn main() {{ println!(\"Hello, Rust!\"); }}")?;
 
         // テスト対象関数を呼び出し
-        let results = read_sources_from_dir(dir.path())?;
+        let diagnostics = DiagnosticCollector::new();
+        let results = read_sources_from_dir(dir.path(), &WalkOptions::default(), &diagnostics)?;
 
-        // 期待値: rsとtxtの2つ
+        // 期待値: ktとrbの2つ (txt / rs は読み込まずにスキップ)
         assert_eq!(results.len(), 2);
+        let summary = diagnostics.summary();
+        assert_eq!(summary.files_scanned, 4);
+        assert_eq!(summary.files_skipped, 2);
 
-        let mut has_rs = false;
-        let mut has_txt = false;
+        let mut has_kt = false;
+        let mut has_rb = false;
 
         for code_file in results {
             match code_file.extension.as_str() {
-                "rs" => {
-                    has_rs = true;
-                    assert!(code_file.code.contains("Hello, Rust!"));
+                "kt" => {
+                    has_kt = true;
+                    assert!(code_file.code.contains("Hello, Kotlin!"));
                 }
-                "txt" => {
-                    has_txt = true;
-                    assert!(code_file.code.contains("Hello, Text!"));
+                "rb" => {
+                    has_rb = true;
+                    assert!(code_file.code.contains("Hello, Ruby!"));
                 }
                 _ => panic!("想定外の拡張子が読み込まれました"),
             }
         }
 
-        assert!(has_rs, ".rs ファイルが見つかりませんでした");
-        assert!(has_txt, ".txt ファイルが見つかりませんでした");
+        assert!(has_kt, ".kt ファイルが見つかりませんでした");
+        assert!(has_rb, ".rb ファイルが見つかりませんでした");
 
         Ok(())
     }
@@ -123,13 +230,17 @@ mod tests {
     fn test_file_path() -> Result<(), Box<dyn Error>> {
         // 一時ディレクトリを作成
         let dir = tempdir()?;
-        // ダミーファイルを作成 (.rs ファイル)
-        let file_path = dir.path().join("docs.rs");
+        // ダミーファイルを作成 (.java ファイル)
+        let file_path = dir.path().join("Docs.java");
         let mut file = File::create(&file_path)?;
-        writeln!(file, "fn sample() {{ println!(\"file_path test\"); }}")?;
+        writeln!(file, "class Docs {{}}")?;
 
         // テスト対象関数を呼び出し
-        let results = read_sources_from_dir(dir.path())?;
+        let results = read_sources_from_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        )?;
         // 期待値: ファイルは1つだけのはず
         assert_eq!(results.len(), 1);
 
@@ -141,4 +252,113 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_sources_binary_and_legacy_encoding() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let (shift_jis, _, _) =
+            encoding_rs::SHIFT_JIS.encode("<?php\n/** @ubiquitous 注文 */\nclass Order {}");
+        fs::write(dir.path().join("Order.php"), &shift_jis)?;
+        // 拡張子は対応言語だが中身はバイナリ
+        fs::write(dir.path().join("Broken.php"), b"\x89PNG\r\n\x1a\n\x00\x00")?;
+
+        // エンコーディング未指定では Shift_JIS のファイルも読み込めない
+        let diagnostics = DiagnosticCollector::new();
+        let results = read_sources_from_dir(dir.path(), &WalkOptions::default(), &diagnostics)?;
+        assert!(results.is_empty());
+        assert_eq!(diagnostics.summary().files_skipped, 1);
+        assert_eq!(diagnostics.summary().files_failed, 1);
+        assert!(diagnostics.diagnostics().iter().any(|diagnostic| matches!(
+            &diagnostic.error,
+            UbiDocError::Binary { file_path } if file_path.ends_with("Broken.php")
+        )));
+
+        let options = WalkOptions {
+            encodings: vec![encoding_rs::SHIFT_JIS],
+            ..WalkOptions::default()
+        };
+        let diagnostics = DiagnosticCollector::new();
+        let results = read_sources_from_dir(dir.path(), &options, &diagnostics)?;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].code.contains("@ubiquitous 注文"));
+        assert_eq!(diagnostics.summary().files_failed, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_sources_with_filter() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("src"))?;
+        fs::create_dir_all(dir.path().join("vendor/acme"))?;
+        File::create(dir.path().join("src/Order.php"))?;
+        File::create(dir.path().join("vendor/acme/Lib.php"))?;
+
+        let options = WalkOptions {
+            filter: PathFilter::new(&[], &["vendor".to_string()])?,
+            ..WalkOptions::default()
+        };
+        let results = read_sources_from_dir(dir.path(), &options, &DiagnosticCollector::new())?;
+
+        // vendor ディレクトリ配下は読み込まれない
+        assert_eq!(results.len(), 1);
+        assert!(results[0].file_path.ends_with("Order.php"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_sources_respect_gitignore() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("src"))?;
+        fs::create_dir_all(dir.path().join("node_modules/lib"))?;
+        fs::write(dir.path().join(".gitignore"), "node_modules/\n*.gen.php\n")?;
+        File::create(dir.path().join("src/Order.php"))?;
+        File::create(dir.path().join("src/Order.gen.php"))?;
+        File::create(dir.path().join("node_modules/lib/Lib.php"))?;
+
+        // デフォルトでは .gitignore に書かれたファイルを読み込まない
+        let results = read_sources_from_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        )?;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].file_path.ends_with("Order.php"));
+
+        // 無効にするとすべてのファイルを読み込む (.gitignore 自体は拡張子がないので対象外)
+        let options = WalkOptions {
+            respect_ignore_files: false,
+            ..WalkOptions::default()
+        };
+        let results = read_sources_from_dir(dir.path(), &options, &DiagnosticCollector::new())?;
+        assert_eq!(results.len(), 3);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_sources_follow_symlinks() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let shared = tempdir()?;
+        File::create(shared.path().join("Shared.php"))?;
+        std::os::unix::fs::symlink(shared.path(), dir.path().join("shared"))?;
+
+        let results = read_sources_from_dir(
+            dir.path(),
+            &WalkOptions::default(),
+            &DiagnosticCollector::new(),
+        )?;
+        assert_eq!(results.len(), 0);
+
+        let options = WalkOptions {
+            follow_symlinks: true,
+            ..WalkOptions::default()
+        };
+        let results = read_sources_from_dir(dir.path(), &options, &DiagnosticCollector::new())?;
+        assert_eq!(results.len(), 1);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,90 @@
+use encoding_rs::Encoding;
+
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+
+use super::path_filter::PathFilter;
+
+/// ソースコード走査時のオプション
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    /// include / exclude の glob
+    pub filter: PathFilter,
+    /// .gitignore / .ignore に書かれたファイルを除外するかどうか
+    pub respect_ignore_files: bool,
+    /// シンボリックリンクをたどるかどうか
+    pub follow_symlinks: bool,
+    /// UTF-8 でデコードできなかった場合に試すエンコーディング
+    pub encodings: Vec<&'static Encoding>,
+}
+
+impl WalkOptions {
+    pub fn from_config(config: &UbiDocConfig) -> Result<Self, UbiDocError> {
+        let filter = PathFilter::new(&config.include, &config.exclude)
+            .map_err(|e| UbiDocError::Config(format!("Invalid include/exclude pattern: {}", e)))?;
+        let encodings = config
+            .encodings
+            .iter()
+            .map(|label| {
+                Encoding::for_label(label.as_bytes())
+                    .ok_or_else(|| UbiDocError::Config(format!("Unknown encoding: {}", label)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(WalkOptions {
+            filter,
+            respect_ignore_files: config.respect_ignore_files.unwrap_or(true),
+            follow_symlinks: config.follow_symlinks.unwrap_or(false),
+            encodings,
+        })
+    }
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        WalkOptions {
+            filter: PathFilter::default(),
+            respect_ignore_files: true,
+            follow_symlinks: false,
+            encodings: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_options_from_config() {
+        let config = UbiDocConfig {
+            respect_ignore_files: Some(false),
+            follow_symlinks: Some(true),
+            ..UbiDocConfig::default()
+        };
+
+        let options = WalkOptions::from_config(&config).unwrap();
+
+        assert!(!options.respect_ignore_files);
+        assert!(options.follow_symlinks);
+    }
+
+    #[test]
+    fn test_walk_options_encodings() {
+        let config = UbiDocConfig {
+            encodings: vec!["Shift_JIS".to_string(), "euc-jp".to_string()],
+            ..UbiDocConfig::default()
+        };
+        let options = WalkOptions::from_config(&config).unwrap();
+        assert_eq!(
+            options.encodings,
+            vec![encoding_rs::SHIFT_JIS, encoding_rs::EUC_JP]
+        );
+
+        let config = UbiDocConfig {
+            encodings: vec!["unknown".to_string()],
+            ..UbiDocConfig::default()
+        };
+        assert!(WalkOptions::from_config(&config).is_err());
+    }
+}
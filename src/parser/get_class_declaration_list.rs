@@ -0,0 +1,91 @@
+use rayon::prelude::*;
+use std::path::Path;
+
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::{
+    diagnostic::Severity, diagnostic_collector::DiagnosticCollector, ubi_doc_error::UbiDocError,
+};
+
+use super::{
+    class_doc::ClassDeclaration,
+    doc_comment::CommentStyle,
+    file_io::{read_sources_from_dir::read_sources_from_dir, walk_options::WalkOptions},
+    get_ubiquitous_list::FileType,
+    java::get_ubiquitous::get_class_declarations as java_declarations,
+    kotlin::get_ubiquitous::get_class_declarations as kotlin_declarations,
+    php::get_ubiquitous::get_class_declarations as php_declarations,
+    ruby::get_ubiquitous::get_class_declarations as ruby_declarations,
+};
+
+/// ファイルパスと言語を付与したクラス宣言
+#[derive(Debug)]
+pub struct SourceClassDeclaration {
+    pub declaration: ClassDeclaration,
+    pub file_path: String,
+    pub language: &'static str,
+    pub comment_style: CommentStyle,
+}
+
+/// ディレクトリ配下の対応言語のファイルから、すべてのクラス宣言を収集する
+pub fn get_class_declaration_list(
+    path: &Path,
+    config: &UbiDocConfig,
+    options: &WalkOptions,
+    diagnostics: &DiagnosticCollector,
+) -> Vec<SourceClassDeclaration> {
+    let code_files = match read_sources_from_dir(path, options, diagnostics) {
+        Ok(files) => files,
+        Err(e) => {
+            diagnostics.report(Severity::Error, e);
+            return vec![];
+        }
+    };
+
+    code_files
+        .par_iter()
+        .map(|code_file| {
+            let file_type = FileType::from_extension(&code_file.extension);
+            let language = match file_type.language_name() {
+                Some(language) if config.is_language_enabled(language) => language,
+                Some(language) => {
+                    diagnostics.file_skipped(
+                        Severity::Info,
+                        UbiDocError::LanguageDisabled {
+                            file_path: code_file.file_path.clone(),
+                            language,
+                        },
+                    );
+                    return Vec::new();
+                }
+                None => return Vec::new(),
+            };
+            let comment_style = file_type.comment_style();
+            let (code, file_path) = (&code_file.code, &code_file.file_path);
+            let declarations = match file_type {
+                FileType::Php => php_declarations(code, file_path),
+                FileType::Kotlin => kotlin_declarations(code, file_path),
+                FileType::Ruby => ruby_declarations(code, file_path, &config.tags),
+                FileType::Java => java_declarations(code, file_path),
+                FileType::Other(_) => Ok(Vec::new()),
+            };
+            let declarations = match declarations {
+                Ok(declarations) => declarations,
+                Err(e) => {
+                    diagnostics.file_failed(e);
+                    return Vec::new();
+                }
+            };
+
+            declarations
+                .into_iter()
+                .map(|declaration| SourceClassDeclaration {
+                    declaration,
+                    file_path: code_file.file_path.clone(),
+                    language,
+                    comment_style,
+                })
+                .collect::<Vec<_>>()
+        })
+        .flatten()
+        .collect()
+}
@@ -1,14 +1,18 @@
 use rayon::prelude::*;
 use std::path::Path;
 
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::{
+    diagnostic::Severity, diagnostic_collector::DiagnosticCollector, ubi_doc_error::UbiDocError,
+};
 use crate::parser::{
-    file_io::read_sources_from_dir::read_sources_from_dir,
+    file_io::{read_sources_from_dir::read_sources_from_dir, walk_options::WalkOptions},
     php::get_ubiquitous::get_ubiquitous as php_parser,
 };
 
 use super::ruby::get_ubiquitous::get_ubiquitous as ruby_paser;
 use super::{
-    java::get_ubiquitous::get_ubiquitous as java_parser,
+    doc_comment::CommentStyle, java::get_ubiquitous::get_ubiquitous as java_parser,
     kotlin::get_ubiquitous::get_ubiquitous as kotlin_paser,
     ubiquitous::Ubiquitous,
 };
@@ -33,37 +37,99 @@ impl FileType {
             other => FileType::Other(other.to_string()),
         }
     }
+
+    /// 対応言語の表示名を返す (未対応の拡張子は None)
+    pub fn language_name(&self) -> Option<&'static str> {
+        match self {
+            FileType::Php => Some("PHP"),
+            FileType::Kotlin => Some("Kotlin"),
+            FileType::Ruby => Some("Ruby"),
+            FileType::Java => Some("Java"),
+            FileType::Other(_) => None,
+        }
+    }
+
+    /// Docコメントの記法
+    pub fn comment_style(&self) -> CommentStyle {
+        match self {
+            FileType::Ruby => CommentStyle::Hash,
+            _ => CommentStyle::Block,
+        }
+    }
 }
 
-pub fn get_ubiquitous_list(path: &Path) -> Vec<Ubiquitous> {
-    let code_files = match read_sources_from_dir(path) {
+/// ディレクトリ配下のソースコードから用語を収集する
+/// 読み込み・解析に失敗したファイルは diagnostics に記録し、残りのファイルの結果を返す
+pub fn get_ubiquitous_list(
+    path: &Path,
+    config: &UbiDocConfig,
+    options: &WalkOptions,
+    diagnostics: &DiagnosticCollector,
+) -> Vec<Ubiquitous> {
+    let code_files = match read_sources_from_dir(path, options, diagnostics) {
         Ok(files) => files,
-        Err(_) => return vec![],
+        Err(e) => {
+            diagnostics.report(Severity::Error, e);
+            return vec![];
+        }
     };
+    let tags = &config.tags;
 
     // parallel execution
     let all_results: Vec<Ubiquitous> = code_files
         .par_iter()
         .map(|code_file| {
-            match FileType::from_extension(&code_file.extension) {
+            let file_type = FileType::from_extension(&code_file.extension);
+            if let Some(language) = file_type.language_name() {
+                if !config.is_language_enabled(language) {
+                    diagnostics.file_skipped(
+                        Severity::Info,
+                        UbiDocError::LanguageDisabled {
+                            file_path: code_file.file_path.clone(),
+                            language,
+                        },
+                    );
+                    return Vec::new();
+                }
+            }
+            let result = match file_type {
                 FileType::Php => {
                     // PHP ファイルの場合の処理（Vec 型の結果を返す）
-                    php_parser(&code_file.code, &code_file.file_path)
+                    php_parser(&code_file.code, &code_file.file_path, tags)
                 }
                 FileType::Kotlin => {
                     // Kotlin ファイルへの処理
-                    kotlin_paser(&code_file.code, &code_file.file_path)
+                    kotlin_paser(&code_file.code, &code_file.file_path, tags)
                 }
-                FileType::Ruby => ruby_paser(&code_file.code, &code_file.file_path),
+                FileType::Ruby => ruby_paser(&code_file.code, &code_file.file_path, tags),
                 FileType::Java => {
                     // Java ファイルへの処理
-                    java_parser(&code_file.code, &code_file.file_path)
+                    java_parser(&code_file.code, &code_file.file_path, tags)
+                }
+                FileType::Other(_ext) => Ok(Vec::new()),
+            };
+            match result {
+                Ok(list) => list,
+                Err(e) => {
+                    diagnostics.file_failed(e);
+                    Vec::new()
                 }
-                FileType::Other(_ext) => Vec::new(),
             }
         })
         .flatten()
         .collect();
 
+    // タグだけで用語が書かれていないものを警告する
+    for ubiquitous in all_results.iter().filter(|u| u.ubiquitous.is_empty()) {
+        diagnostics.report(
+            Severity::Warning,
+            UbiDocError::EmptyTerm {
+                file_path: ubiquitous.file_path.clone().unwrap_or_default(),
+                line_number: ubiquitous.line_number.unwrap_or_default(),
+            },
+        );
+    }
+    diagnostics.terms_found(all_results.len());
+
     all_results
 }
@@ -1,42 +1,192 @@
 use tree_sitter::Node;
 
-#[derive(Debug)]
-pub struct ClassDoc {
-    pub class_name: String,
-    pub doc_comment: String,
-    pub doc_comment_line: Option<usize>,
-}
+use crate::parser::{
+    class_doc::{ClassDeclaration, ClassDoc, DeclarationKind},
+    scope::Scope,
+    type_references::collect_type_references,
+};
 
 pub fn collect_class_docs(node: Node, source_code: &[u8]) -> Vec<ClassDoc> {
+    collect_class_declarations(node, source_code)
+        .into_iter()
+        .filter_map(ClassDeclaration::into_class_doc)
+        .collect()
+}
+
+/// Docコメントの有無にかかわらず、すべてのクラス宣言を返す
+pub fn collect_class_declarations(node: Node, source_code: &[u8]) -> Vec<ClassDeclaration> {
+    collect_declarations_in_scope(node, source_code, &Scope::new(".", "."))
+}
+
+/// scope: package 宣言と外側のクラス
+fn collect_declarations_in_scope(
+    node: Node,
+    source_code: &[u8],
+    scope: &Scope,
+) -> Vec<ClassDeclaration> {
     let mut results = Vec::new();
+    let mut child_scope = scope.clone();
 
-    // Java の class_declaration ノードを検出
-    if node.kind() == "class_declaration" {
+    // Java の class / interface / enum / record 宣言を検出
+    if let Some(kind) = declaration_kind(node) {
         if let Some(id_node) = find_class_identifier_top_level(node) {
             if let Ok(class_name) = id_node.utf8_text(source_code) {
-                // クラス直前の JavaDoc/コメントを探す
-                if let Some((doc_comment, doc_comment_line)) =
-                    find_preceding_doc_comment_java(node, source_code)
-                {
-                    results.push(ClassDoc {
-                        class_name: class_name.to_string(),
-                        doc_comment,
-                        doc_comment_line: Some(doc_comment_line),
-                    });
-                }
+                let qualified_name = scope.qualify(class_name);
+                // 内部クラスは外側のクラス名を含めた名前にする
+                child_scope = scope.enter_class(&qualified_name);
+                results.push(ClassDeclaration {
+                    class_name: class_name.to_string(),
+                    kind,
+                    qualified_name,
+                    parent: None,
+                    line_number: node.start_position().row + 1,
+                    // クラス直前の JavaDoc/コメントを探す
+                    doc_comment: find_preceding_doc_comment_java(node, source_code),
+                    namespace: scope.namespace.clone(),
+                    type_references: collect_type_references(
+                        node,
+                        source_code,
+                        &is_type_reference,
+                        &|node| declaration_kind(node).is_some(),
+                    ),
+                });
             }
         }
     }
 
-    // 子ノードを再帰的に探索
+    // メソッドはドメインの操作として、宣言しているクラスとともに記録する
+    if node.kind() == "method_declaration" {
+        if let Some(method_name) = node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok())
+        {
+            results.push(ClassDeclaration {
+                class_name: method_name.to_string(),
+                kind: DeclarationKind::Operation,
+                qualified_name: scope.qualify_member(method_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_java(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // enum の列挙子は宣言している enum とともに記録する
+    if node.kind() == "enum_constant" {
+        if let Some(case_name) = node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok())
+        {
+            results.push(ClassDeclaration {
+                class_name: case_name.to_string(),
+                kind: DeclarationKind::EnumCase,
+                qualified_name: scope.qualify_member(case_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_java(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // フィールドは宣言しているクラスとともに記録する (static final は定数とみなす)
+    // インターフェースのフィールド (constant_declaration) は暗黙に static final なので定数とする
+    if matches!(node.kind(), "field_declaration" | "constant_declaration") {
+        if let Some(field_name) = field_name(node, source_code) {
+            let kind = match node.kind() {
+                "constant_declaration" => DeclarationKind::Constant,
+                _ => field_kind(node, source_code),
+            };
+            results.push(ClassDeclaration {
+                class_name: field_name.to_string(),
+                kind,
+                qualified_name: scope.qualify_member(field_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_java(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // 子ノードを再帰的に探索 (package 宣言より後のクラスはそのパッケージに属する)
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            results.extend(collect_class_docs(child, source_code));
+            if child.kind() == "package_declaration" {
+                child_scope = child_scope.with_namespace(package_name(child, source_code));
+            }
+            results.extend(collect_declarations_in_scope(
+                child,
+                source_code,
+                &child_scope,
+            ));
         }
     }
     results
 }
 
+/// クラスの中で型名を表すノード (List<OrderLine> の OrderLine、billing.Customer など)
+fn is_type_reference(node: Node) -> bool {
+    matches!(node.kind(), "type_identifier" | "scoped_type_identifier")
+}
+
+/// 型の宣言ノードであればその種類を返す
+fn declaration_kind(node: Node) -> Option<DeclarationKind> {
+    match node.kind() {
+        "class_declaration" => Some(DeclarationKind::Class),
+        "interface_declaration" | "annotation_type_declaration" => Some(DeclarationKind::Interface),
+        "enum_declaration" => Some(DeclarationKind::Enum),
+        "record_declaration" => Some(DeclarationKind::Record),
+        _ => None,
+    }
+}
+
+/// field_declaration / constant_declaration ノードから最初の変数名を取り出す (int a, b; の場合は a)
+fn field_name<'a>(field_decl: Node, source_code: &'a [u8]) -> Option<&'a str> {
+    let mut cursor = field_decl.walk();
+    let declarator = field_decl
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "variable_declarator")?;
+    declarator
+        .child_by_field_name("name")?
+        .utf8_text(source_code)
+        .ok()
+}
+
+/// static final なフィールドは定数、それ以外はプロパティとする
+fn field_kind(field_decl: Node, source_code: &[u8]) -> DeclarationKind {
+    let mut cursor = field_decl.walk();
+    let is_constant = field_decl
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+        .and_then(|modifiers| modifiers.utf8_text(source_code).ok())
+        .is_some_and(|modifiers| {
+            let words: Vec<_> = modifiers.split_whitespace().collect();
+            words.contains(&"static") && words.contains(&"final")
+        });
+    if is_constant {
+        DeclarationKind::Constant
+    } else {
+        DeclarationKind::Property
+    }
+}
+
+/// package_declaration ノードからパッケージ名 (例: com.acme.ordering) を取り出す
+fn package_name(package_node: Node, source_code: &[u8]) -> Option<String> {
+    let mut cursor = package_node.walk();
+    let name_node = package_node
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "scoped_identifier" | "identifier"))?;
+    name_node
+        .utf8_text(source_code)
+        .ok()
+        .map(|name| name.split_whitespace().collect())
+}
+
 /// class_declaration ノードの「直下の子」を順に確認し、
 /// 最初に見つかった 'identifier' を返す。
 fn find_class_identifier_top_level(class_decl: Node) -> Option<Node> {
@@ -68,10 +218,7 @@ fn find_preceding_doc_comment_java(node: Node, source_code: &[u8]) -> Option<(St
                 }
             }
         }
-        if prev.is_named()
-            && kind != "comment"
-            && kind != "line_comment"
-            && kind != "block_comment"
+        if prev.is_named() && kind != "comment" && kind != "line_comment" && kind != "block_comment"
         {
             break;
         }
@@ -83,6 +230,8 @@ fn find_preceding_doc_comment_java(node: Node, source_code: &[u8]) -> Option<(St
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ubi_doc_config::TagConfig;
+    use crate::parser::doc_comment::CommentStyle;
     use tree_sitter::Parser;
     use tree_sitter_java;
 
@@ -211,4 +360,242 @@ mod tests {
             "BarクラスのJavaDocが取れていること"
         );
     }
+
+    #[test]
+    fn test_collect_class_declarations_package() {
+        let code = r#"
+        package com.acme.ordering.domain;
+
+        public class Order {
+            class Line {}
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 2);
+        assert_eq!(
+            declarations[0].qualified_name,
+            "com.acme.ordering.domain.Order"
+        );
+        // 内部クラスは外側のクラス名を含む
+        assert_eq!(declarations[1].class_name, "Line");
+        assert_eq!(
+            declarations[1].qualified_name,
+            "com.acme.ordering.domain.Order.Line"
+        );
+        for declaration in declarations.iter() {
+            assert_eq!(
+                declaration.namespace.as_deref(),
+                Some("com.acme.ordering.domain")
+            );
+        }
+    }
+
+    #[test]
+    fn test_collect_class_declarations_kinds() {
+        let code = r#"
+        interface Payable {}
+        enum Status { OPEN, CLOSED }
+        record Money(int amount) {}
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let kinds: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| !declaration.kind.is_member())
+            .map(|declaration| (declaration.class_name.as_str(), declaration.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Payable", DeclarationKind::Interface),
+                ("Status", DeclarationKind::Enum),
+                ("Money", DeclarationKind::Record),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_class_declarations_methods() {
+        let code = r#"
+        package com.acme.ordering;
+
+        public class Order {
+            /**
+             * @ubiquitous 注文する
+             */
+            @Deprecated
+            public void place() {}
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 2);
+        let place = &declarations[1];
+        assert_eq!(place.class_name, "place");
+        assert_eq!(place.kind, DeclarationKind::Operation);
+        assert_eq!(place.qualified_name, "com.acme.ordering.Order.place");
+        assert_eq!(place.parent.as_deref(), Some("com.acme.ordering.Order"));
+        assert!(place.has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_fields() {
+        let code = r#"
+        public class Order {
+            /**
+             * @ubiquitous 配送先住所
+             */
+            private Address shippingAddress, billingAddress;
+
+            static final int MAX_LINES = 10;
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].class_name, "shippingAddress");
+        assert_eq!(declarations[1].kind, DeclarationKind::Property);
+        assert_eq!(declarations[1].qualified_name, "Order.shippingAddress");
+        assert_eq!(declarations[1].parent.as_deref(), Some("Order"));
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert_eq!(declarations[2].class_name, "MAX_LINES");
+        assert_eq!(declarations[2].kind, DeclarationKind::Constant);
+    }
+
+    #[test]
+    fn test_collect_class_declarations_interface_constants() {
+        let code = r#"
+        public interface OrderPolicy {
+            /**
+             * @ubiquitous 注文上限
+             */
+            int MAX_ORDERS = 100;
+
+            void check(Order order);
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].class_name, "MAX_ORDERS");
+        assert_eq!(declarations[1].kind, DeclarationKind::Constant);
+        assert_eq!(declarations[1].qualified_name, "OrderPolicy.MAX_ORDERS");
+        assert_eq!(declarations[1].parent.as_deref(), Some("OrderPolicy"));
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert_eq!(declarations[2].kind, DeclarationKind::Operation);
+    }
+
+    #[test]
+    fn test_collect_class_declarations_enum_constants() {
+        let code = r#"
+        package com.acme.ordering;
+
+        /**
+         * @ubiquitous 注文ステータス
+         */
+        enum Status {
+            /**
+             * @ubiquitous 保留中
+             */
+            PENDING("p"),
+            SHIPPED;
+
+            Status() {}
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let cases: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| declaration.kind == DeclarationKind::EnumCase)
+            .collect();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].class_name, "PENDING");
+        assert_eq!(cases[0].qualified_name, "com.acme.ordering.Status.PENDING");
+        assert_eq!(cases[0].parent.as_deref(), Some("com.acme.ordering.Status"));
+        assert!(cases[0].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert!(!cases[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_type_references() {
+        let code = r#"
+        class Order extends Base {
+            private Money total;
+            private List<OrderLine> lines;
+
+            Invoice bill(billing.Customer customer) {
+                return new Invoice(customer);
+            }
+
+            class Line {
+                private Shipment shipment;
+            }
+        }
+        "#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_java::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(
+            declarations[0].type_references,
+            vec![
+                "Base",
+                "Money",
+                "List",
+                "OrderLine",
+                "Invoice",
+                "billing.Customer"
+            ]
+        );
+        // メンバーには型名を記録しない
+        assert!(declarations[1].type_references.is_empty());
+        // ネストしたクラスの中の型名は、外側のクラスではなくネストしたクラスに記録する
+        let line = declarations
+            .iter()
+            .find(|declaration| declaration.class_name == "Line")
+            .unwrap();
+        assert_eq!(line.type_references, vec!["Shipment"]);
+    }
 }
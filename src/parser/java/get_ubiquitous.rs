@@ -1,24 +1,24 @@
-use tree_sitter::Parser;
+use tree_sitter::{Parser, Tree};
 
-use crate::parser::ubiquitous::Ubiquitous;
-
-use super::{
-    collect_class_docs::collect_class_docs,
+use crate::config::ubi_doc_config::TagConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::parser::{
+    class_doc::ClassDeclaration,
+    doc_comment::CommentStyle,
     extract_ubiquitous::{extract_ubiquitous, ExtractUbiquitousParam},
+    ubiquitous::Ubiquitous,
 };
 
-pub fn get_ubiquitous(code: &str, file_path: &str) -> Vec<Ubiquitous> {
-    let source_code = code.as_bytes();
+use super::collect_class_docs::{collect_class_declarations, collect_class_docs};
 
-    // Java 言語用のパーサを作成
-    let mut parser = Parser::new();
-    // Java の言語定義を設定
-    let language = tree_sitter_java::LANGUAGE;
-    parser
-        .set_language(&language.into())
-        .expect("Error loading Java grammar");
+pub fn get_ubiquitous(
+    code: &str,
+    file_path: &str,
+    tags: &TagConfig,
+) -> Result<Vec<Ubiquitous>, UbiDocError> {
+    let source_code = code.as_bytes();
 
-    let tree = parser.parse(source_code, None).unwrap();
+    let tree = parse(code, file_path)?;
     let root_node = tree.root_node();
 
     // ノードを再帰的に巡回しコメントを収集
@@ -28,13 +28,49 @@ pub fn get_ubiquitous(code: &str, file_path: &str) -> Vec<Ubiquitous> {
         .into_iter()
         .map(|doc| ExtractUbiquitousParam {
             class_name: doc.class_name,
+            kind: doc.kind,
+            qualified_name: doc.qualified_name,
+            parent: doc.parent,
             doc_comment: doc.doc_comment,
+            comment_style: CommentStyle::Block,
             file_path: file_path.to_string(),
             line_number: doc.doc_comment_line.unwrap_or(0),
+            namespace: doc.namespace,
+            type_references: doc.type_references,
         })
         .collect();
 
     // コメント文字列をクリーニング
-    let result = extract_ubiquitous(params);
-    result
+    Ok(extract_ubiquitous(params, tags))
+}
+
+/// Docコメントの有無にかかわらず、ファイル内のすべてのクラス宣言を返す
+pub fn get_class_declarations(
+    code: &str,
+    file_path: &str,
+) -> Result<Vec<ClassDeclaration>, UbiDocError> {
+    let tree = parse(code, file_path)?;
+    Ok(collect_class_declarations(
+        tree.root_node(),
+        code.as_bytes(),
+    ))
+}
+
+/// ソースコードをパースして構文木を生成する
+fn parse(code: &str, file_path: &str) -> Result<Tree, UbiDocError> {
+    // Java 言語用のパーサを作成
+    let mut parser = Parser::new();
+    // Java の言語定義を設定
+    let language = tree_sitter_java::LANGUAGE;
+    parser
+        .set_language(&language.into())
+        .map_err(|e| UbiDocError::Parse {
+            file_path: file_path.to_string(),
+            message: format!("Error loading Java grammar: {}", e),
+        })?;
+
+    parser.parse(code, None).ok_or_else(|| UbiDocError::Parse {
+        file_path: file_path.to_string(),
+        message: "parser returned no tree".to_string(),
+    })
 }
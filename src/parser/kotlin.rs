@@ -1,3 +1,2 @@
 mod collect_class_docs;
-mod extract_ubiquitous;
 pub mod get_ubiquitous;
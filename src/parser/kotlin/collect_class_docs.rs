@@ -1,42 +1,212 @@
 use tree_sitter::Node;
 
-#[derive(Debug)]
-pub struct ClassDoc {
-    pub class_name: String,
-    pub doc_comment: String,
-    pub doc_comment_line: Option<usize>,
-}
+use crate::parser::{
+    class_doc::{ClassDeclaration, ClassDoc, DeclarationKind},
+    scope::Scope,
+    type_references::collect_type_references,
+};
 
 pub fn collect_class_docs(node: Node, source_code: &[u8]) -> Vec<ClassDoc> {
+    collect_class_declarations(node, source_code)
+        .into_iter()
+        .filter_map(ClassDeclaration::into_class_doc)
+        .collect()
+}
+
+/// Docコメントの有無にかかわらず、すべてのクラス宣言を返す
+pub fn collect_class_declarations(node: Node, source_code: &[u8]) -> Vec<ClassDeclaration> {
+    collect_declarations_in_scope(node, source_code, &Scope::new(".", "."))
+}
+
+/// scope: package 宣言と外側のクラス
+fn collect_declarations_in_scope(
+    node: Node,
+    source_code: &[u8],
+    scope: &Scope,
+) -> Vec<ClassDeclaration> {
     let mut results = Vec::new();
+    let mut child_scope = scope.clone();
 
-    // Kotlin の class_declaration ノードを検出
-    if node.kind() == "class_declaration" {
+    // Kotlin の class / interface / enum class / object 宣言を検出
+    if let Some(kind) = declaration_kind(node, source_code) {
         if let Some(id_node) = find_class_identifier_top_level(node) {
             if let Ok(class_name) = id_node.utf8_text(source_code) {
-                // クラス直前の KDoc/コメントを探す
-                if let Some((doc_comment, doc_comment_line)) =
-                    find_preceding_doc_comment_kotlin(node, source_code)
-                {
-                    results.push(ClassDoc {
-                        class_name: class_name.to_string(),
-                        doc_comment,
-                        doc_comment_line: Some(doc_comment_line),
-                    });
-                }
+                let qualified_name = scope.qualify(class_name);
+                // 内部クラスは外側のクラス名を含めた名前にする
+                child_scope = scope.enter_class(&qualified_name);
+                results.push(ClassDeclaration {
+                    class_name: class_name.to_string(),
+                    kind,
+                    qualified_name,
+                    parent: None,
+                    line_number: node.start_position().row + 1,
+                    // クラス直前の KDoc/コメントを探す
+                    doc_comment: find_preceding_doc_comment_kotlin(node, source_code),
+                    namespace: scope.namespace.clone(),
+                    type_references: collect_type_references(
+                        node,
+                        source_code,
+                        &is_type_reference,
+                        &is_declaration,
+                    ),
+                });
             }
         }
     }
 
-    // 子ノードを再帰的に探索
+    // 関数はドメインの操作として、宣言しているクラス (トップレベルの関数では None) とともに記録する
+    if node.kind() == "function_declaration" {
+        let mut cursor = node.walk();
+        let function_name = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "simple_identifier")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok());
+        if let Some(function_name) = function_name {
+            results.push(ClassDeclaration {
+                class_name: function_name.to_string(),
+                kind: DeclarationKind::Operation,
+                qualified_name: scope.qualify_member(function_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_kotlin(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // enum class の列挙子は宣言している enum とともに記録する
+    if node.kind() == "enum_entry" {
+        let mut cursor = node.walk();
+        let case_name = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "simple_identifier")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok());
+        if let Some(case_name) = case_name {
+            results.push(ClassDeclaration {
+                class_name: case_name.to_string(),
+                kind: DeclarationKind::EnumCase,
+                qualified_name: scope.qualify_member(case_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_kotlin(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // プロパティは宣言しているクラスとともに記録する (const val は定数とみなす)
+    if node.kind() == "property_declaration" {
+        if let Some(property_name) = property_name(node, source_code) {
+            results.push(ClassDeclaration {
+                class_name: property_name.to_string(),
+                kind: property_kind(node, source_code),
+                qualified_name: scope.qualify_member(property_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment_kotlin(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // 子ノードを再帰的に探索 (package 宣言より後のクラスはそのパッケージに属する)
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            results.extend(collect_class_docs(child, source_code));
+            if child.kind() == "package_header" {
+                child_scope = child_scope.with_namespace(package_name(child, source_code));
+            }
+            results.extend(collect_declarations_in_scope(
+                child,
+                source_code,
+                &child_scope,
+            ));
         }
     }
     results
 }
 
+/// クラスの中で型名を表すノード (継承・プロパティ・引数・戻り値の型)
+/// 修飾された型名 (billing.Customer) は user_type 全体を1つの型名とする
+fn is_type_reference(node: Node) -> bool {
+    node.kind() == "user_type"
+}
+
+/// ネストしたクラス・object の宣言 (companion object はクラスの一部として扱う)
+fn is_declaration(node: Node) -> bool {
+    matches!(node.kind(), "class_declaration" | "object_declaration")
+}
+
+/// 型の宣言ノードであればその種類を返す
+/// Kotlin では interface / enum class も class_declaration になるため、キーワードと修飾子で判定する
+fn declaration_kind(node: Node, source_code: &[u8]) -> Option<DeclarationKind> {
+    match node.kind() {
+        "object_declaration" => Some(DeclarationKind::Object),
+        "class_declaration" => {
+            let mut cursor = node.walk();
+            let mut kind = DeclarationKind::Class;
+            for child in node.children(&mut cursor) {
+                match child.kind() {
+                    "interface" => kind = DeclarationKind::Interface,
+                    "enum_class_body" => kind = DeclarationKind::Enum,
+                    "modifiers"
+                        if child.utf8_text(source_code).is_ok_and(|modifiers| {
+                            modifiers.split_whitespace().any(|word| word == "enum")
+                        }) =>
+                    {
+                        kind = DeclarationKind::Enum
+                    }
+                    _ => {}
+                }
+            }
+            Some(kind)
+        }
+        _ => None,
+    }
+}
+
+/// property_declaration ノードからプロパティ名を取り出す
+fn property_name<'a>(property_decl: Node, source_code: &'a [u8]) -> Option<&'a str> {
+    let mut cursor = property_decl.walk();
+    let variable = property_decl
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "variable_declaration")?;
+    let mut cursor = variable.walk();
+    let name_node = variable
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "simple_identifier")?;
+    name_node.utf8_text(source_code).ok()
+}
+
+/// const val は定数、それ以外はプロパティとする
+fn property_kind(property_decl: Node, source_code: &[u8]) -> DeclarationKind {
+    let mut cursor = property_decl.walk();
+    let is_constant = property_decl
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+        .and_then(|modifiers| modifiers.utf8_text(source_code).ok())
+        .is_some_and(|modifiers| modifiers.split_whitespace().any(|word| word == "const"));
+    if is_constant {
+        DeclarationKind::Constant
+    } else {
+        DeclarationKind::Property
+    }
+}
+
+/// package_header ノードからパッケージ名 (例: com.acme.ordering) を取り出す
+fn package_name(package_node: Node, source_code: &[u8]) -> Option<String> {
+    let mut cursor = package_node.walk();
+    let name_node = package_node
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "identifier"))?;
+    name_node
+        .utf8_text(source_code)
+        .ok()
+        .map(|name| name.split_whitespace().collect())
+}
+
 /// class_declaration ノードの「直下の子」を順に確認し、
 /// 最初に見つかった 'type_identifier' または 'identifier' を返す。
 fn find_class_identifier_top_level(class_decl: Node) -> Option<Node> {
@@ -84,6 +254,8 @@ fn find_preceding_doc_comment_kotlin(node: Node, source_code: &[u8]) -> Option<(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ubi_doc_config::TagConfig;
+    use crate::parser::doc_comment::CommentStyle;
     use tree_sitter::Parser;
     use tree_sitter_kotlin as kotlin;
 
@@ -208,4 +380,186 @@ mod tests {
             "BarクラスのKDocが取れていること"
         );
     }
+
+    #[test]
+    fn test_collect_class_declarations_package() {
+        let code = r#"
+        package com.acme.ordering.domain
+
+        class Order
+        "#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 1);
+        assert_eq!(
+            declarations[0].namespace.as_deref(),
+            Some("com.acme.ordering.domain")
+        );
+        assert_eq!(
+            declarations[0].qualified_name,
+            "com.acme.ordering.domain.Order"
+        );
+    }
+
+    #[test]
+    fn test_collect_class_declarations_kinds() {
+        let code = r#"
+interface Payable
+enum class Status { OPEN, CLOSED }
+object Registry
+"#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let kinds: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| !declaration.kind.is_member())
+            .map(|declaration| (declaration.class_name.as_str(), declaration.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Payable", DeclarationKind::Interface),
+                ("Status", DeclarationKind::Enum),
+                ("Registry", DeclarationKind::Object),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_class_declarations_functions() {
+        let code = r#"
+package com.acme.ordering
+
+class Order {
+    /**
+     * @ubiquitous 注文する
+     */
+    fun place() {}
+}
+
+fun cancel() {}
+"#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].kind, DeclarationKind::Operation);
+        assert_eq!(
+            declarations[1].qualified_name,
+            "com.acme.ordering.Order.place"
+        );
+        assert_eq!(
+            declarations[1].parent.as_deref(),
+            Some("com.acme.ordering.Order")
+        );
+        // トップレベルの関数には宣言しているクラスがない
+        assert_eq!(declarations[2].qualified_name, "com.acme.ordering.cancel");
+        assert_eq!(declarations[2].parent, None);
+    }
+
+    #[test]
+    fn test_collect_class_declarations_properties() {
+        let code = r#"
+class Order {
+    /**
+     * @ubiquitous 配送先住所
+     */
+    val shippingAddress: Address? = null
+
+    companion object {
+        const val MAX_LINES = 10
+    }
+}
+"#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let members: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| declaration.kind.is_member())
+            .collect();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].kind, DeclarationKind::Property);
+        assert_eq!(members[0].qualified_name, "Order.shippingAddress");
+        assert_eq!(members[0].parent.as_deref(), Some("Order"));
+        assert_eq!(members[1].kind, DeclarationKind::Constant);
+    }
+
+    #[test]
+    fn test_collect_class_declarations_enum_entries() {
+        let code = r#"
+enum class Status {
+    /**
+     * @ubiquitous 保留中
+     */
+    PENDING,
+    SHIPPED
+}
+"#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let cases: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| declaration.kind == DeclarationKind::EnumCase)
+            .collect();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].qualified_name, "Status.PENDING");
+        assert_eq!(cases[0].parent.as_deref(), Some("Status"));
+        assert!(cases[0].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_type_references() {
+        let code = r#"
+class Order(val total: Money) : Base() {
+    val lines: List<billing.OrderLine> = emptyList()
+
+    fun bill(customer: Customer): Invoice = Invoice(customer)
+
+    class Line(val shipment: Shipment)
+}
+"#;
+
+        let mut parser = Parser::new();
+        parser.set_language(&kotlin::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        // 修飾された型名は1つの型名とし、ネストしたクラスの中の型名は含めない
+        assert_eq!(
+            declarations[0].type_references,
+            vec![
+                "Money",
+                "Base",
+                "List",
+                "billing.OrderLine",
+                "Customer",
+                "Invoice"
+            ]
+        );
+    }
 }
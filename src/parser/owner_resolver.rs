@@ -0,0 +1,212 @@
+use std::path::Path;
+
+use crate::config::ubi_doc_config::UbiDocConfig;
+use crate::diagnostics::ubi_doc_error::UbiDocError;
+use crate::parser::code_owners::CodeOwners;
+use crate::parser::parent_chain::find_in_parents;
+use crate::parser::ubiquitous::Ubiquitous;
+
+/// @owner が書かれていない用語の担当チームを CODEOWNERS から決める
+/// codeowners が設定されていればそのファイルを、なければ入力ディレクトリ・カレントディレクトリの
+/// CODEOWNERS を使う (複数見つかった場合は入力ディレクトリのものを優先する)
+#[derive(Debug)]
+pub struct OwnerResolver {
+    code_owners: Vec<CodeOwners>,
+}
+
+impl OwnerResolver {
+    pub fn from_config(config: &UbiDocConfig) -> Result<Self, UbiDocError> {
+        if let Some(path) = config.codeowners.as_deref() {
+            return Ok(OwnerResolver {
+                code_owners: vec![CodeOwners::load(Path::new(path))?],
+            });
+        }
+
+        let mut code_owners: Vec<CodeOwners> = Vec::new();
+        let dirs = config.input_paths().into_iter().chain([".".to_string()]);
+        for dir in dirs {
+            let Some(path) = CodeOwners::discover(Path::new(&dir)) else {
+                continue;
+            };
+            if code_owners
+                .iter()
+                .any(|loaded| same_file(&loaded.path, &path))
+            {
+                continue;
+            }
+            code_owners.push(CodeOwners::load(&path)?);
+        }
+        Ok(OwnerResolver { code_owners })
+    }
+
+    /// ファイルの担当チーム (最初に一致した CODEOWNERS のもの)
+    pub fn resolve(&self, file_path: &str) -> Option<&[String]> {
+        self.code_owners
+            .iter()
+            .find_map(|code_owners| code_owners.owners_of(file_path))
+    }
+
+    /// @owner が書かれていない用語に、CODEOWNERS の担当チームを設定する
+    pub fn apply(&self, list: Vec<Ubiquitous>) -> Vec<Ubiquitous> {
+        if self.code_owners.is_empty() {
+            return list;
+        }
+        list.into_iter()
+            .map(|ubiquitous| {
+                if !ubiquitous.teams.is_empty() {
+                    return ubiquitous;
+                }
+                let teams = ubiquitous
+                    .file_path
+                    .as_deref()
+                    .and_then(|file_path| self.resolve(file_path))
+                    .unwrap_or_default()
+                    .to_vec();
+                teams
+                    .into_iter()
+                    .fold(ubiquitous, |ubiquitous, team| ubiquitous.add_team(team))
+            })
+            .collect()
+    }
+}
+
+/// @owner が書かれていないメソッド・列挙子などに、宣言しているクラスの担当チームを設定する
+/// 入れ子のクラスの中で宣言されている場合は、担当チームが見つかるまで外側へたどる
+pub fn inherit_teams(list: Vec<Ubiquitous>) -> Vec<Ubiquitous> {
+    let inherited = find_in_parents(&list, |ubiquitous| {
+        (!ubiquitous.teams.is_empty()).then(|| ubiquitous.teams.clone())
+    });
+    list.into_iter()
+        .zip(inherited)
+        .map(|(ubiquitous, teams)| {
+            if !ubiquitous.teams.is_empty() {
+                return ubiquitous;
+            }
+            teams
+                .unwrap_or_default()
+                .into_iter()
+                .fold(ubiquitous, |ubiquitous, team| ubiquitous.add_team(team))
+        })
+        .collect()
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_apply_owner() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::write(
+            dir.path().join("CODEOWNERS"),
+            "* @acme/architects\n/billing/ @acme/billing\n",
+        )?;
+        let input = dir.path().to_string_lossy().to_string();
+        let config = UbiDocConfig {
+            input: vec![input.clone()],
+            ..UbiDocConfig::default()
+        };
+        let resolver = OwnerResolver::from_config(&config)?;
+
+        let list = resolver.apply(vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Invoice".to_string())
+                .set_file_path(format!("{}/billing/Invoice.php", input)),
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .add_team("@acme/sales".to_string())
+                .set_file_path(format!("{}/billing/Order.php", input)),
+            Ubiquitous::new().set_ubiquitous("Money".to_string()),
+        ]);
+
+        assert_eq!(list[0].teams, vec!["@acme/billing"]);
+        // @owner が書かれていればそちらを優先する
+        assert_eq!(list[1].teams, vec!["@acme/sales"]);
+        assert!(list[2].teams.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_owner_absolute_input() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(repo.join("billing"))?;
+        fs::create_dir(dir.path().join("config"))?;
+        fs::write(repo.join("CODEOWNERS"), "/billing/ @acme/billing\n")?;
+        fs::write(repo.join("billing/Invoice.php"), "<?php\n")?;
+        // CODEOWNERS は設定ファイルのディレクトリからの相対パス、入力ディレクトリは絶対パスで指定する
+        let input = repo.canonicalize()?;
+        let config = UbiDocConfig {
+            codeowners: Some("../repo/CODEOWNERS".to_string()),
+            ..UbiDocConfig::default()
+        }
+        .resolve_paths(&dir.path().join("config"));
+        let config = UbiDocConfig {
+            input: vec![input.to_string_lossy().to_string()],
+            ..config
+        };
+        let resolver = OwnerResolver::from_config(&config)?;
+
+        let list = resolver.apply(vec![Ubiquitous::new()
+            .set_ubiquitous("Invoice".to_string())
+            .set_file_path(
+                input
+                    .join("billing/Invoice.php")
+                    .to_string_lossy()
+                    .to_string(),
+            )]);
+
+        assert_eq!(list[0].teams, vec!["@acme/billing"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_inherit_teams() {
+        let list = inherit_teams(vec![
+            Ubiquitous::new()
+                .set_ubiquitous("Order".to_string())
+                .set_qualified_name("App\\Order".to_string())
+                .add_team("@acme/sales".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Place Order".to_string())
+                .set_qualified_name("App\\Order::place".to_string())
+                .set_parent("App\\Order".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Cancel Order".to_string())
+                .set_parent("App\\Order".to_string())
+                .add_team("@acme/support".to_string()),
+            Ubiquitous::new()
+                .set_ubiquitous("Format".to_string())
+                .set_parent("App\\Helpers".to_string()),
+        ]);
+
+        assert_eq!(list[1].teams, vec!["@acme/sales"]);
+        // @owner が書かれていればそちらを優先する
+        assert_eq!(list[2].teams, vec!["@acme/support"]);
+        // 宣言しているクラスが用語集にない
+        assert!(list[3].teams.is_empty());
+    }
+
+    #[test]
+    fn test_missing_codeowners() {
+        let config = UbiDocConfig {
+            codeowners: Some("not-found/CODEOWNERS".to_string()),
+            ..UbiDocConfig::default()
+        };
+
+        assert!(matches!(
+            OwnerResolver::from_config(&config),
+            Err(UbiDocError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use crate::parser::ubiquitous::Ubiquitous;
+
+/// 用語ごとに、用語自身から宣言しているクラス (parent) へ外側にたどり、最初に見つかった値を返す
+/// メソッド・列挙子などに書かれていない @context や @owner をクラスから引き継ぐのに使う
+pub fn find_in_parents<T>(
+    list: &[Ubiquitous],
+    value: impl Fn(&Ubiquitous) -> Option<T>,
+) -> Vec<Option<T>> {
+    let declarations: HashMap<&str, &Ubiquitous> = list
+        .iter()
+        .filter_map(|ubiquitous| Some((ubiquitous.qualified_name.as_deref()?, ubiquitous)))
+        .collect();
+    list.iter()
+        .map(|ubiquitous| {
+            let mut current = ubiquitous;
+            // 循環していても止まるように、たどる回数を用語の数までにする
+            for _ in 0..list.len() {
+                if let Some(found) = value(current) {
+                    return Some(found);
+                }
+                current = current
+                    .parent
+                    .as_deref()
+                    .and_then(|parent| declarations.get(parent))?;
+            }
+            None
+        })
+        .collect()
+}
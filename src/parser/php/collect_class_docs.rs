@@ -1,45 +1,185 @@
 use tree_sitter::Node;
 
-/// (クラス名, Docコメント) を格納するための型
-pub struct ClassDoc {
-    pub class_name: String,
-    pub doc_comment: String,
-    pub doc_comment_line: Option<usize>,
-}
+use crate::parser::{
+    class_doc::{ClassDeclaration, ClassDoc, DeclarationKind},
+    scope::Scope,
+    type_references::collect_type_references,
+};
 
 /// クラス宣言に付随している Docコメントを探し出し、(クラス名, Docコメント) のリストを返す
 pub fn collect_class_docs(node: Node, source_code: &[u8]) -> Vec<ClassDoc> {
+    collect_class_declarations(node, source_code)
+        .into_iter()
+        .filter_map(ClassDeclaration::into_class_doc)
+        .collect()
+}
+
+/// Docコメントの有無にかかわらず、すべてのクラス宣言を返す
+pub fn collect_class_declarations(node: Node, source_code: &[u8]) -> Vec<ClassDeclaration> {
+    collect_declarations_in_scope(node, source_code, &Scope::new("\\", "::"))
+}
+
+/// scope: クラス宣言を囲む名前空間
+fn collect_declarations_in_scope(
+    node: Node,
+    source_code: &[u8],
+    scope: &Scope,
+) -> Vec<ClassDeclaration> {
     let mut results = Vec::new();
+    let mut scope = scope.clone();
 
-    // 自身がクラス宣言ノードかどうか
-    if node.kind() == "class_declaration" {
+    // 自身がクラス・インターフェース・トレイト・enum の宣言ノードかどうか
+    if let Some(kind) = declaration_kind(node) {
         // ツリーシッターPHPでは class_declaration の中に "name" というフィールドがあるのでそれを探す
         if let Some(name_node) = node.child_by_field_name("name") {
             if let Ok(class_name) = name_node.utf8_text(source_code) {
-                // クラス直前にある DocBlock コメントを探す
-                if let Some((doc_comment, doc_comment_line)) =
-                    find_preceding_doc_comment(node, source_code)
-                {
-                    results.push(ClassDoc {
-                        class_name: class_name.to_string(),
-                        doc_comment,
-                        doc_comment_line: Some(doc_comment_line),
-                    });
-                }
+                let qualified_name = scope.qualify(class_name);
+                results.push(ClassDeclaration {
+                    class_name: class_name.to_string(),
+                    kind,
+                    qualified_name: qualified_name.clone(),
+                    parent: None,
+                    line_number: node.start_position().row + 1,
+                    // クラス直前にある DocBlock コメントを探す
+                    doc_comment: find_preceding_doc_comment(node, source_code),
+                    namespace: scope.namespace.clone(),
+                    type_references: collect_type_references(
+                        node,
+                        source_code,
+                        &is_type_reference,
+                        &|node| declaration_kind(node).is_some(),
+                    ),
+                });
+                // クラス内のメソッドはこのクラスで宣言されたものとする
+                scope = scope.enter_class(&qualified_name);
             }
         }
     }
 
+    // メソッド・関数はドメインの操作として、宣言しているクラス (関数では None) とともに記録する
+    if matches!(node.kind(), "method_declaration" | "function_definition") {
+        if let Some(function_name) = node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok())
+        {
+            results.push(ClassDeclaration {
+                class_name: function_name.to_string(),
+                kind: DeclarationKind::Operation,
+                qualified_name: scope.qualify_member(function_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comment(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // プロパティ・クラス定数・enum の case は宣言しているクラスとともに記録する
+    if let Some((member_name, kind)) = member_declaration(node, source_code) {
+        // プロパティは PHP の表記に合わせて $ を付ける (例: App\Order::$total)
+        let qualified_name = match kind {
+            DeclarationKind::Property => scope.qualify_member(&format!("${}", member_name)),
+            _ => scope.qualify_member(member_name),
+        };
+        results.push(ClassDeclaration {
+            class_name: member_name.to_string(),
+            kind,
+            qualified_name,
+            parent: scope.parent.clone(),
+            line_number: node.start_position().row + 1,
+            doc_comment: find_preceding_doc_comment(node, source_code),
+            namespace: scope.namespace.clone(),
+            type_references: Vec::new(),
+        });
+    }
+
     // 子ノードを再帰的に探索
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            results.extend(collect_class_docs(child, source_code));
+            if child.kind() == "namespace_definition" {
+                let name = child
+                    .child_by_field_name("name")
+                    .and_then(|name_node| name_node.utf8_text(source_code).ok())
+                    .map(str::to_string);
+                // namespace Foo { ... } はブロック内だけ、namespace Foo; は以降のすべてのクラスに適用する
+                if child.child_by_field_name("body").is_some() {
+                    results.extend(collect_declarations_in_scope(
+                        child,
+                        source_code,
+                        &scope.with_namespace(name),
+                    ));
+                    continue;
+                }
+                scope = scope.with_namespace(name);
+            }
+            results.extend(collect_declarations_in_scope(child, source_code, &scope));
         }
     }
 
     results
 }
 
+/// プロパティ・クラス定数・enum の case の宣言ノードであれば、最初のメンバー名と種類を返す
+fn member_declaration<'a>(node: Node, source_code: &'a [u8]) -> Option<(&'a str, DeclarationKind)> {
+    let (element_kind, kind) = match node.kind() {
+        "property_declaration" => ("property_element", DeclarationKind::Property),
+        "const_declaration" => ("const_element", DeclarationKind::Constant),
+        "enum_case" => {
+            let name = node
+                .child_by_field_name("name")?
+                .utf8_text(source_code)
+                .ok()?;
+            return Some((name, DeclarationKind::EnumCase));
+        }
+        _ => return None,
+    };
+    let mut cursor = node.walk();
+    let element = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == element_kind)?;
+    // property_element は variable_name ($total) の中に name (total) を持つ
+    let mut cursor = element.walk();
+    let name_node = element
+        .named_children(&mut cursor)
+        .find_map(|child| match child.kind() {
+            "name" => Some(child),
+            "variable_name" => child.named_child(0),
+            _ => None,
+        })?;
+    let name = name_node.utf8_text(source_code).ok()?;
+    Some((name, kind))
+}
+
+/// クラスの中で型名を表すノード
+/// 型宣言に加えて、extends / implements・new・静的呼び出し (Tax::rate()) のクラス名も含める
+fn is_type_reference(node: Node) -> bool {
+    if node.kind() == "named_type" {
+        return true;
+    }
+    if !matches!(node.kind(), "name" | "qualified_name") {
+        return false;
+    }
+    node.parent().is_some_and(|parent| match parent.kind() {
+        "base_clause" | "class_interface_clause" | "object_creation_expression" => true,
+        "scoped_call_expression" | "class_constant_access_expression" => {
+            parent.named_child(0) == Some(node)
+        }
+        _ => false,
+    })
+}
+
+/// 型の宣言ノードであればその種類を返す
+fn declaration_kind(node: Node) -> Option<DeclarationKind> {
+    match node.kind() {
+        "class_declaration" => Some(DeclarationKind::Class),
+        "interface_declaration" => Some(DeclarationKind::Interface),
+        "trait_declaration" => Some(DeclarationKind::Trait),
+        "enum_declaration" => Some(DeclarationKind::Enum),
+        _ => None,
+    }
+}
+
 /// クラス宣言ノードの直前にある Docコメント(コメントノード)を探して返す
 fn find_preceding_doc_comment(node: Node, source_code: &[u8]) -> Option<(String, usize)> {
     // 兄弟ノードを逆方向にたどりながらコメントを探す
@@ -70,6 +210,8 @@ fn find_preceding_doc_comment(node: Node, source_code: &[u8]) -> Option<(String,
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ubi_doc_config::TagConfig;
+    use crate::parser::doc_comment::CommentStyle;
     use tree_sitter::Parser;
 
     #[test]
@@ -121,7 +263,7 @@ class Foo {
 class Bar {}
 
 /**
- * This is not a class
+ * This is a function
  */
 function baz() {}
 "#;
@@ -135,8 +277,8 @@ function baz() {}
 
         let docs = collect_class_docs(root_node, code.as_bytes());
 
-        // Foo と Bar の2クラスが取得される
-        assert_eq!(docs.len(), 2);
+        // Foo と Bar の2クラスと、関数 baz が取得される
+        assert_eq!(docs.len(), 3);
 
         assert_eq!(docs[0].class_name, "Foo");
         assert!(docs[0].doc_comment.contains("Doc for Foo"));
@@ -144,6 +286,9 @@ function baz() {}
         assert_eq!(docs[1].class_name, "Bar");
         assert!(docs[1].doc_comment.contains("Doc for Bar"));
         assert_eq!(docs[1].doc_comment_line, Some(9));
+        assert_eq!(docs[2].class_name, "baz");
+        assert_eq!(docs[2].kind, DeclarationKind::Operation);
+        assert_eq!(docs[2].parent, None);
     }
 
     #[test]
@@ -195,4 +340,218 @@ class WithDocBlock {}
         assert!(docs[0].doc_comment.contains("Actual DocBlock"));
         assert_eq!(docs[0].doc_comment_line, Some(5));
     }
+
+    #[test]
+    fn test_collect_class_declarations_with_and_without_doc() {
+        let code = r#"
+<?php
+/**
+ * @ubiquitous Foo
+ */
+class Foo {}
+
+class NoDocClass {}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+
+        let tree = parser.parse(code, None).unwrap();
+        let root_node = tree.root_node();
+
+        let declarations = collect_class_declarations(root_node, code.as_bytes());
+
+        // Docコメントの有無にかかわらず 2 件取得される
+        assert_eq!(declarations.len(), 2);
+        assert_eq!(declarations[0].class_name, "Foo");
+        assert_eq!(declarations[0].line_number, 6);
+        assert!(declarations[0].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert_eq!(declarations[1].class_name, "NoDocClass");
+        assert_eq!(declarations[1].line_number, 8);
+        assert!(declarations[1].doc_comment.is_none());
+        assert!(!declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_namespace() {
+        let code = r#"<?php
+namespace App\Ordering\Domain;
+
+class Order {}
+
+namespace App\Billing {
+    class Invoice {}
+}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 2);
+        assert_eq!(
+            declarations[0].namespace.as_deref(),
+            Some(r"App\Ordering\Domain")
+        );
+        assert_eq!(declarations[1].namespace.as_deref(), Some(r"App\Billing"));
+        assert_eq!(declarations[0].qualified_name, r"App\Ordering\Domain\Order");
+        assert_eq!(declarations[1].qualified_name, r"App\Billing\Invoice");
+    }
+
+    #[test]
+    fn test_collect_class_declarations_kinds() {
+        let code = r#"<?php
+interface Payable {}
+trait Timestamps {}
+enum Status { case Open; }
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        let kinds: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| !declaration.kind.is_member())
+            .map(|declaration| (declaration.class_name.as_str(), declaration.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Payable", DeclarationKind::Interface),
+                ("Timestamps", DeclarationKind::Trait),
+                ("Status", DeclarationKind::Enum),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_class_declarations_methods() {
+        let code = r#"<?php
+namespace App\Sales;
+
+class Order {
+    /**
+     * @ubiquitous 注文する
+     */
+    public function place() {}
+}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 2);
+        let place = &declarations[1];
+        assert_eq!(place.class_name, "place");
+        assert_eq!(place.kind, DeclarationKind::Operation);
+        assert_eq!(place.qualified_name, r"App\Sales\Order::place");
+        assert_eq!(place.parent.as_deref(), Some(r"App\Sales\Order"));
+        assert_eq!(place.namespace.as_deref(), Some(r"App\Sales"));
+        assert!(place.has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_properties_and_constants() {
+        let code = r#"<?php
+class Order {
+    /**
+     * @ubiquitous 合計金額
+     */
+    public ?int $grandTotal = 0;
+
+    const MAX_LINES = 10;
+}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].class_name, "grandTotal");
+        assert_eq!(declarations[1].kind, DeclarationKind::Property);
+        assert_eq!(declarations[1].qualified_name, "Order::$grandTotal");
+        assert_eq!(declarations[1].parent.as_deref(), Some("Order"));
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert_eq!(declarations[2].kind, DeclarationKind::Constant);
+        assert_eq!(declarations[2].qualified_name, "Order::MAX_LINES");
+    }
+
+    #[test]
+    fn test_collect_class_declarations_enum_cases() {
+        let code = r#"<?php
+/**
+ * @ubiquitous 注文ステータス
+ */
+enum Status: string {
+    /**
+     * @ubiquitous 保留中
+     */
+    case Pending = 'pending';
+    case Shipped = 'shipped';
+}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].kind, DeclarationKind::EnumCase);
+        assert_eq!(declarations[1].qualified_name, "Status::Pending");
+        assert_eq!(declarations[1].parent.as_deref(), Some("Status"));
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Block));
+        assert_eq!(declarations[2].class_name, "Shipped");
+    }
+
+    #[test]
+    fn test_collect_class_declarations_type_references() {
+        let code = r#"<?php
+class Order extends Base implements Payable {
+    private Money $total;
+
+    public function bill(\App\Billing\Customer $customer): Invoice {
+        Tax::rate();
+        return new Invoice($customer);
+    }
+}
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_php::LANGUAGE_PHP;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations = collect_class_declarations(tree.root_node(), code.as_bytes());
+
+        assert_eq!(
+            declarations[0].type_references,
+            vec![
+                "Base",
+                "Payable",
+                "Money",
+                "\\App\\Billing\\Customer",
+                "Invoice",
+                "Tax"
+            ]
+        );
+    }
 }
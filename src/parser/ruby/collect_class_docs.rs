@@ -1,43 +1,209 @@
 use tree_sitter::Node;
 
-#[derive(Debug)]
-pub struct ClassDoc {
-    pub class_name: String,
-    pub doc_comment: String,
-    pub doc_comment_line: Option<usize>,
+use crate::config::ubi_doc_config::TagConfig;
+use crate::parser::{
+    class_doc::{ClassDeclaration, ClassDoc, DeclarationKind},
+    doc_comment::CommentStyle,
+    scope::Scope,
+    type_references::collect_type_references,
+};
+
+pub fn collect_class_docs(node: Node, source_code: &[u8], tags: &TagConfig) -> Vec<ClassDoc> {
+    collect_class_declarations(node, source_code, tags)
+        .into_iter()
+        .filter_map(ClassDeclaration::into_class_doc)
+        .collect()
 }
 
-pub fn collect_class_docs(node: Node, source_code: &[u8]) -> Vec<ClassDoc> {
+/// Docコメントの有無にかかわらず、すべてのクラス宣言を返す
+pub fn collect_class_declarations(
+    node: Node,
+    source_code: &[u8],
+    tags: &TagConfig,
+) -> Vec<ClassDeclaration> {
+    let mut declarations = collect_declarations_in_scope(node, source_code, &Scope::new("::", "#"));
+
+    // Ruby には enum がないため、@enum を付けた module を enum、その直下の定数を列挙子とみなす
+    let mut enums: Vec<String> = Vec::new();
+    for declaration in declarations.iter_mut() {
+        if declaration.kind == DeclarationKind::Module
+            && declaration.has_tag(&tags.enumeration, tags, CommentStyle::Hash)
+        {
+            declaration.kind = DeclarationKind::Enum;
+            enums.push(declaration.qualified_name.clone());
+        }
+    }
+    for declaration in declarations.iter_mut() {
+        if declaration.kind == DeclarationKind::Constant
+            && declaration
+                .parent
+                .as_ref()
+                .is_some_and(|parent| enums.contains(parent))
+        {
+            declaration.kind = DeclarationKind::EnumCase;
+        }
+    }
+    declarations
+}
+
+/// scope: クラスを囲む module (ネスト順に :: でつないだもの。例: Acme::Ordering) と外側のクラス
+fn collect_declarations_in_scope(
+    node: Node,
+    source_code: &[u8],
+    scope: &Scope,
+) -> Vec<ClassDeclaration> {
     let mut results = Vec::new();
+    let mut child_scope = scope.clone();
 
     // Ruby の class ノードを検出
     if node.kind() == "class" {
         if let Some(id_node) = find_class_identifier_top_level(node) {
             if let Ok(class_name) = id_node.utf8_text(source_code) {
-                // クラス直前のコメントを探す
-                if let Some((doc_comment, doc_comment_line)) =
-                    find_preceding_doc_comments_ruby(node, source_code)
-                {
-                    results.push(ClassDoc {
-                        class_name: class_name.to_string(),
-                        doc_comment,
-                        doc_comment_line: Some(doc_comment_line),
-                    });
-                }
+                let qualified_name = scope.qualify(class_name);
+                child_scope = scope.enter_class(&qualified_name);
+                results.push(ClassDeclaration {
+                    class_name: class_name.to_string(),
+                    kind: DeclarationKind::Class,
+                    qualified_name,
+                    parent: None,
+                    line_number: node.start_position().row + 1,
+                    // クラス直前のコメントを探す
+                    doc_comment: find_preceding_doc_comments_ruby(node, source_code),
+                    namespace: scope.namespace.clone(),
+                    type_references: collect_type_references(
+                        node,
+                        source_code,
+                        &is_type_reference,
+                        &is_declaration,
+                    ),
+                });
             }
         }
     }
 
+    // module 自体も用語の宣言とし、中はそのモジュール名を付けて探索する
+    if node.kind() == "module" {
+        if let Some(name) = node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok())
+        {
+            results.push(ClassDeclaration {
+                class_name: name.to_string(),
+                kind: DeclarationKind::Module,
+                qualified_name: scope.qualify(name),
+                parent: None,
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comments_ruby(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+            child_scope = scope.enter_module(name);
+        }
+    }
+
+    // メソッド (def / def self.) はドメインの操作として、宣言しているクラス・モジュールとともに記録する
+    if matches!(node.kind(), "method" | "singleton_method") {
+        if let Some(method_name) = node
+            .child_by_field_name("name")
+            .and_then(|name_node| name_node.utf8_text(source_code).ok())
+        {
+            results.push(ClassDeclaration {
+                class_name: method_name.to_string(),
+                kind: DeclarationKind::Operation,
+                qualified_name: scope.qualify_member(method_name),
+                parent: scope.parent.clone(),
+                line_number: node.start_position().row + 1,
+                doc_comment: find_preceding_doc_comments_ruby(node, source_code),
+                namespace: scope.namespace.clone(),
+                type_references: Vec::new(),
+            });
+        }
+    }
+
+    // attr_reader などの属性と定数は宣言しているクラス・モジュールとともに記録する
+    if let Some((member_name, kind)) = member_declaration(node, source_code) {
+        // 属性は Order#shipping_address、定数は Order::MAX と表記する
+        let qualified_name = match kind {
+            DeclarationKind::Constant => scope.qualify(member_name),
+            _ => scope.qualify_member(member_name),
+        };
+        results.push(ClassDeclaration {
+            class_name: member_name.to_string(),
+            kind,
+            qualified_name,
+            parent: scope.parent.clone(),
+            line_number: node.start_position().row + 1,
+            doc_comment: find_preceding_doc_comments_ruby(node, source_code),
+            namespace: scope.namespace.clone(),
+            type_references: Vec::new(),
+        });
+    }
+
     // 子ノードを再帰的に探索
     for i in 0..node.child_count() {
         if let Some(child) = node.child(i) {
-            results.extend(collect_class_docs(child, source_code));
+            results.extend(collect_declarations_in_scope(
+                child,
+                source_code,
+                &child_scope,
+            ));
         }
     }
 
     results
 }
 
+/// attr_reader / attr_writer / attr_accessor の呼び出しであれば最初の属性名を、
+/// 定数への代入であれば定数名を、種類とともに返す
+fn member_declaration<'a>(node: Node, source_code: &'a [u8]) -> Option<(&'a str, DeclarationKind)> {
+    match node.kind() {
+        "call" => {
+            let method = node
+                .child_by_field_name("method")?
+                .utf8_text(source_code)
+                .ok()?;
+            if !matches!(method, "attr_reader" | "attr_writer" | "attr_accessor") {
+                return None;
+            }
+            let arguments = node.child_by_field_name("arguments")?;
+            let mut cursor = arguments.walk();
+            let symbol = arguments
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "simple_symbol")?;
+            let name = symbol.utf8_text(source_code).ok()?.trim_start_matches(':');
+            Some((name, DeclarationKind::Property))
+        }
+        "assignment" => {
+            let left = node.child_by_field_name("left")?;
+            if left.kind() != "constant" {
+                return None;
+            }
+            Some((left.utf8_text(source_code).ok()?, DeclarationKind::Constant))
+        }
+        _ => None,
+    }
+}
+
+/// クラスの中で参照されている定数 (Money、Billing::Invoice など)
+/// ネストしたクラス・モジュールの名前と、定数の定義 (MAX = 10) の左辺は除く
+fn is_type_reference(node: Node) -> bool {
+    if !matches!(node.kind(), "constant" | "scope_resolution") {
+        return false;
+    }
+    node.parent().is_none_or(|parent| {
+        let is_declaration_name = matches!(parent.kind(), "class" | "module")
+            && parent.child_by_field_name("name") == Some(node);
+        let is_assignment =
+            parent.kind() == "assignment" && parent.child_by_field_name("left") == Some(node);
+        !is_declaration_name && !is_assignment
+    })
+}
+
+/// ネストしたクラス・モジュールの宣言
+fn is_declaration(node: Node) -> bool {
+    matches!(node.kind(), "class" | "module")
+}
+
 /// class ノードの「直下の子」を順に確認し、
 /// 最初に見つかった 'constant' を返す。
 fn find_class_identifier_top_level(class_decl: Node) -> Option<Node> {
@@ -61,7 +227,12 @@ fn find_preceding_doc_comments_ruby(node: Node, source_code: &[u8]) -> Option<(S
     // ここで型注釈を追加する
     let mut earliest_line: Option<usize> = None;
 
-    let mut current = node.prev_sibling();
+    // module / class 本体の先頭にある宣言のコメントは body_statement の外側にある
+    let mut current = node.prev_sibling().or_else(|| {
+        node.parent()
+            .filter(|parent| parent.kind() == "body_statement")
+            .and_then(|parent| parent.prev_sibling())
+    });
 
     while let Some(prev_node) = current {
         let kind = prev_node.kind();
@@ -116,7 +287,7 @@ mod tests {
         let tree = parser.parse(code, None).expect("Failed to parse code");
         let root_node = tree.root_node();
 
-        let docs = collect_class_docs(root_node, code.as_bytes());
+        let docs = collect_class_docs(root_node, code.as_bytes(), &TagConfig::default());
         assert_eq!(docs.len(), 1, "クラスが1つだけ取得されるはず");
 
         let foo = &docs[0];
@@ -152,7 +323,7 @@ mod tests {
         let tree = parser.parse(code, None).expect("Failed to parse code");
         let root_node = tree.root_node();
 
-        let docs = collect_class_docs(root_node, code.as_bytes());
+        let docs = collect_class_docs(root_node, code.as_bytes(), &TagConfig::default());
         assert_eq!(docs.len(), 2, "2つのクラスが検出されるはず");
 
         let alpha = &docs[0];
@@ -193,7 +364,7 @@ mod tests {
         let tree = parser.parse(code, None).expect("Failed to parse code");
         let root_node = tree.root_node();
 
-        let docs = collect_class_docs(root_node, code.as_bytes());
+        let docs = collect_class_docs(root_node, code.as_bytes(), &TagConfig::default());
 
         // Bar クラス1件だけが取得される (NoDocはコメントなし)
         assert_eq!(docs.len(), 1, "コメント付きの Bar クラスだけが検出される");
@@ -203,4 +374,215 @@ mod tests {
             "Barクラスのコメントが取れていること"
         );
     }
+
+    #[test]
+    fn test_collect_class_declarations_module() {
+        let code = r#"
+module Acme
+  module Ordering::Domain
+    class Order
+      class Line
+      end
+    end
+  end
+
+  class Shared
+  end
+end
+
+class TopLevel
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations =
+            collect_class_declarations(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        assert_eq!(declarations.len(), 6);
+        assert_eq!(declarations[0].qualified_name, "Acme");
+        assert_eq!(declarations[0].kind, DeclarationKind::Module);
+        assert_eq!(declarations[1].qualified_name, "Acme::Ordering::Domain");
+        assert_eq!(declarations[1].namespace.as_deref(), Some("Acme"));
+        assert_eq!(
+            declarations[2].namespace.as_deref(),
+            Some("Acme::Ordering::Domain")
+        );
+        assert_eq!(declarations[2].kind, DeclarationKind::Class);
+        assert_eq!(
+            declarations[3].qualified_name,
+            "Acme::Ordering::Domain::Order::Line"
+        );
+        assert_eq!(
+            declarations[3].namespace.as_deref(),
+            Some("Acme::Ordering::Domain")
+        );
+        assert_eq!(declarations[4].namespace.as_deref(), Some("Acme"));
+        assert_eq!(declarations[4].qualified_name, "Acme::Shared");
+        assert_eq!(declarations[5].namespace, None);
+        assert_eq!(declarations[5].qualified_name, "TopLevel");
+    }
+
+    #[test]
+    fn test_collect_class_docs_module_and_first_class_in_body() {
+        let code = r#"
+# @ubiquitous 受注
+module Ordering
+  # @ubiquitous 注文
+  class Order
+  end
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let docs = collect_class_docs(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].kind, DeclarationKind::Module);
+        assert!(docs[0].doc_comment.contains("受注"));
+        assert_eq!(docs[1].qualified_name, "Ordering::Order");
+        assert!(docs[1].doc_comment.contains("注文"));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_methods() {
+        let code = r#"
+module Billing
+  class Invoice
+    # @ubiquitous 精算する
+    def settle
+    end
+
+    def self.issue
+    end
+  end
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations =
+            collect_class_declarations(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        let operations: Vec<_> = declarations
+            .iter()
+            .filter(|declaration| declaration.kind == DeclarationKind::Operation)
+            .collect();
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].qualified_name, "Billing::Invoice#settle");
+        assert_eq!(operations[0].parent.as_deref(), Some("Billing::Invoice"));
+        assert!(operations[0].has_ubiquitous(&TagConfig::default(), CommentStyle::Hash));
+        assert_eq!(operations[1].class_name, "issue");
+        assert!(!operations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Hash));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_attributes_and_constants() {
+        let code = r#"
+class Order
+  # @ubiquitous 配送先住所
+  attr_reader :shipping_address, :billing_address
+
+  MAX_LINES = 10
+
+  validates :shipping_address
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations =
+            collect_class_declarations(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        // attr_* 以外のメソッド呼び出しは対象外
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[1].class_name, "shipping_address");
+        assert_eq!(declarations[1].kind, DeclarationKind::Property);
+        assert_eq!(declarations[1].qualified_name, "Order#shipping_address");
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Hash));
+        assert_eq!(declarations[2].kind, DeclarationKind::Constant);
+        assert_eq!(declarations[2].qualified_name, "Order::MAX_LINES");
+        assert_eq!(declarations[2].parent.as_deref(), Some("Order"));
+    }
+
+    #[test]
+    fn test_collect_class_declarations_module_constants_as_enum_cases() {
+        let code = r#"
+# @ubiquitous 注文ステータス
+# @enum
+module OrderStatus
+  # @ubiquitous 保留中
+  PENDING = "pending"
+  SHIPPED = "shipped"
+end
+
+module Settings
+  TIMEOUT = 30
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations =
+            collect_class_declarations(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        assert_eq!(declarations.len(), 5);
+        // @enum を付けた module は enum、その直下の定数は列挙子になる
+        assert_eq!(declarations[0].kind, DeclarationKind::Enum);
+        assert_eq!(declarations[1].kind, DeclarationKind::EnumCase);
+        assert_eq!(declarations[1].qualified_name, "OrderStatus::PENDING");
+        assert_eq!(declarations[1].parent.as_deref(), Some("OrderStatus"));
+        assert!(declarations[1].has_ubiquitous(&TagConfig::default(), CommentStyle::Hash));
+        assert_eq!(declarations[2].kind, DeclarationKind::EnumCase);
+        // @enum のない module の定数は定数のまま
+        assert_eq!(declarations[3].kind, DeclarationKind::Module);
+        assert_eq!(declarations[4].kind, DeclarationKind::Constant);
+    }
+
+    #[test]
+    fn test_collect_class_declarations_type_references() {
+        let code = r#"
+class Order < Base
+  MAX_LINES = 10
+
+  class Line
+    belongs_to Shipment
+  end
+
+  def bill(customer)
+    Billing::Invoice.new(customer, Money.zero)
+  end
+end
+"#;
+
+        let mut parser = Parser::new();
+        let language = tree_sitter_ruby::LANGUAGE;
+        parser.set_language(&language.into()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+
+        let declarations =
+            collect_class_declarations(tree.root_node(), code.as_bytes(), &TagConfig::default());
+
+        // 定数の定義と、ネストしたクラスの名前・中で参照している定数は含めない
+        assert_eq!(
+            declarations[0].type_references,
+            vec!["Base", "Billing::Invoice", "Money"]
+        );
+    }
 }
@@ -0,0 +1,120 @@
+/// 走査中のノードを囲むパッケージ・名前空間と外側のクラス
+#[derive(Debug, Clone)]
+pub struct Scope {
+    /// パッケージ・名前空間・モジュール
+    pub namespace: Option<String>,
+    /// 名前空間と外側のクラスをつないだ完全修飾名 (トップレベルでは namespace と同じ)
+    pub qualified_prefix: Option<String>,
+    /// メンバーを宣言しているクラス・モジュールの完全修飾名 (トップレベルでは None)
+    pub parent: Option<String>,
+    /// 完全修飾名の区切り文字 (Java / Kotlin は ".", PHP は "\", Ruby は "::")
+    separator: &'static str,
+    /// クラスとメソッドの区切り文字 (Java / Kotlin は ".", PHP は "::", Ruby は "#")
+    member_separator: &'static str,
+}
+
+impl Scope {
+    pub fn new(separator: &'static str, member_separator: &'static str) -> Self {
+        Scope {
+            namespace: None,
+            qualified_prefix: None,
+            parent: None,
+            separator,
+            member_separator,
+        }
+    }
+
+    /// package / namespace 宣言の中に入る
+    pub fn with_namespace(&self, namespace: Option<String>) -> Self {
+        Scope {
+            qualified_prefix: namespace.clone(),
+            namespace,
+            parent: None,
+            ..self.clone()
+        }
+    }
+
+    /// Ruby の module の中に入る (外側の module に名前をつなげる)
+    pub fn enter_module(&self, name: &str) -> Self {
+        let join = |outer: &Option<String>| Some(self.join(outer.as_deref(), name));
+        let qualified_prefix = join(&self.qualified_prefix);
+        Scope {
+            namespace: join(&self.namespace),
+            parent: qualified_prefix.clone(),
+            qualified_prefix,
+            ..self.clone()
+        }
+    }
+
+    /// クラスの中に入る (内部クラスは外側のクラスの完全修飾名を引き継ぐ)
+    pub fn enter_class(&self, qualified_name: &str) -> Self {
+        Scope {
+            qualified_prefix: Some(qualified_name.to_string()),
+            parent: Some(qualified_name.to_string()),
+            ..self.clone()
+        }
+    }
+
+    /// このスコープで宣言された名前の完全修飾名
+    pub fn qualify(&self, name: &str) -> String {
+        self.join(self.qualified_prefix.as_deref(), name)
+    }
+
+    /// このスコープで宣言されたメソッド・関数の完全修飾名 (例: App\Order::place, Order#place)
+    pub fn qualify_member(&self, name: &str) -> String {
+        match &self.parent {
+            Some(parent) => format!("{}{}{}", parent, self.member_separator, name),
+            None => self.qualify(name),
+        }
+    }
+
+    fn join(&self, outer: Option<&str>, name: &str) -> String {
+        match outer {
+            Some(outer) if !outer.is_empty() => format!("{}{}{}", outer, self.separator, name),
+            _ => name.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualify() {
+        let scope = Scope::new(".", ".");
+        assert_eq!(scope.qualify("Order"), "Order");
+
+        let package = scope.with_namespace(Some("com.acme.ordering".to_string()));
+        let order = package.qualify("Order");
+        assert_eq!(order, "com.acme.ordering.Order");
+
+        let inner = package.enter_class(&order);
+        assert_eq!(inner.qualify("Line"), "com.acme.ordering.Order.Line");
+        assert_eq!(inner.namespace.as_deref(), Some("com.acme.ordering"));
+    }
+
+    #[test]
+    fn test_enter_module() {
+        let scope = Scope::new("::", "#")
+            .enter_module("Acme")
+            .enter_module("Ordering");
+        assert_eq!(scope.namespace.as_deref(), Some("Acme::Ordering"));
+        assert_eq!(scope.qualify("Order"), "Acme::Ordering::Order");
+    }
+
+    #[test]
+    fn test_qualify_member() {
+        let namespace = Scope::new("\\", "::").with_namespace(Some("App\\Sales".to_string()));
+        // トップレベルの関数は名前空間だけを付ける
+        assert_eq!(namespace.parent, None);
+        assert_eq!(namespace.qualify_member("settle"), "App\\Sales\\settle");
+
+        let order = namespace.enter_class("App\\Sales\\Order");
+        assert_eq!(order.parent.as_deref(), Some("App\\Sales\\Order"));
+        assert_eq!(order.qualify_member("place"), "App\\Sales\\Order::place");
+
+        let module = Scope::new("::", "#").enter_module("Billing");
+        assert_eq!(module.qualify_member("settle"), "Billing#settle");
+    }
+}
@@ -0,0 +1,85 @@
+use crate::lint::normalize_term::normalize_term;
+
+/// DDD の構成要素としての用語の種類 (@stereotype / @aggregate / @entity など)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stereotype {
+    /// 集約ルート
+    Aggregate,
+    Entity,
+    ValueObject,
+    /// ドメインイベント
+    DomainEvent,
+    /// ドメインサービス
+    Service,
+    Policy,
+}
+
+impl Stereotype {
+    /// @stereotype の値から種類を判定する
+    /// 大文字・小文字、空白・ハイフン・アンダースコアの違いは無視する (value object / valueObject / value_object)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = normalize_term(name)
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_'))
+            .collect();
+        match name.as_str() {
+            "aggregate" | "aggregateroot" => Some(Stereotype::Aggregate),
+            "entity" => Some(Stereotype::Entity),
+            "valueobject" => Some(Stereotype::ValueObject),
+            "event" | "domainevent" => Some(Stereotype::DomainEvent),
+            "service" | "domainservice" => Some(Stereotype::Service),
+            "policy" => Some(Stereotype::Policy),
+            _ => None,
+        }
+    }
+
+    /// JSON・HTML の class 名に使う値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stereotype::Aggregate => "aggregate",
+            Stereotype::Entity => "entity",
+            Stereotype::ValueObject => "value-object",
+            Stereotype::DomainEvent => "event",
+            Stereotype::Service => "service",
+            Stereotype::Policy => "policy",
+        }
+    }
+
+    /// 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stereotype::Aggregate => "Aggregate",
+            Stereotype::Entity => "Entity",
+            Stereotype::ValueObject => "Value Object",
+            Stereotype::DomainEvent => "Domain Event",
+            Stereotype::Service => "Service",
+            Stereotype::Policy => "Policy",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            Stereotype::from_name("Aggregate Root"),
+            Some(Stereotype::Aggregate)
+        );
+        assert_eq!(
+            Stereotype::from_name("valueObject"),
+            Some(Stereotype::ValueObject)
+        );
+        assert_eq!(
+            Stereotype::from_name("value_object"),
+            Some(Stereotype::ValueObject)
+        );
+        assert_eq!(
+            Stereotype::from_name("Domain Event"),
+            Some(Stereotype::DomainEvent)
+        );
+        assert_eq!(Stereotype::from_name("repository"), None);
+    }
+}
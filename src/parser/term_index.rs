@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::lint::normalize_term::normalize_term;
+
+use super::{term_reference::TermReference, ubiquitous::Ubiquitous};
+
+/// 用語集の中から参照先の用語を探すための索引
+pub struct TermIndex {
+    entries: Vec<TermEntry>,
+    /// 出力しない用語 (--owner で除いた用語) のリンク先
+    hidden: HashSet<String>,
+}
+
+/// 索引に登録された用語1件分
+struct TermEntry {
+    /// 用語名・別名・翻訳した用語名・クラス名・完全修飾名を正規化したもの
+    keys: Vec<String>,
+    context: String,
+    /// HTML のリンク先 (行の id)
+    anchor: String,
+}
+
+impl TermIndex {
+    pub fn new(ubiquitous_list: &[Ubiquitous]) -> Self {
+        let mut anchor_counts: HashMap<String, usize> = HashMap::new();
+        let entries = ubiquitous_list
+            .iter()
+            .map(|ubiquitous| {
+                let translated = ubiquitous
+                    .translations
+                    .values()
+                    .filter_map(|translation| translation.ubiquitous.as_ref());
+                let keys = std::iter::once(&ubiquitous.ubiquitous)
+                    .chain(ubiquitous.aliases.iter())
+                    .chain(translated)
+                    .chain(ubiquitous.class_name.iter())
+                    .chain(ubiquitous.qualified_name.iter())
+                    .map(|key| normalize_term(key))
+                    .filter(|key| !key.is_empty())
+                    .collect();
+                let context = ubiquitous.context.clone().unwrap_or_default();
+
+                // 同じ id にならないように、2つ目以降には連番を付ける
+                let anchor = anchor_slug(&context, &ubiquitous.ubiquitous);
+                let count = anchor_counts.entry(anchor.clone()).or_default();
+                *count += 1;
+                let anchor = match *count {
+                    1 => anchor,
+                    n => format!("{}-{}", anchor, n),
+                };
+
+                TermEntry {
+                    keys,
+                    context: normalize_term(&context),
+                    anchor,
+                }
+            })
+            .collect();
+        TermIndex {
+            entries,
+            hidden: HashSet::new(),
+        }
+    }
+
+    /// ubiquitous_list の index 番目の用語を出力しない用語にする
+    /// 参照先としては見つかるが、リンクにはしない
+    pub fn hide(&mut self, index: usize) {
+        self.hidden.insert(self.entries[index].anchor.clone());
+    }
+
+    /// 出力しない用語のリンク先かどうか
+    pub fn is_hidden(&self, anchor: &str) -> bool {
+        self.hidden.contains(anchor)
+    }
+
+    /// ubiquitous_list の index 番目の用語のリンク先
+    pub fn anchor(&self, index: usize) -> &str {
+        &self.entries[index].anchor
+    }
+
+    /// 参照先の用語のリンク先を返す (見つからなければ None)
+    /// 同じ名前の用語が複数ある場合は、参照元と同じコンテキストの用語を優先する
+    pub fn resolve(&self, reference: &str, from_context: Option<&str>) -> Option<&str> {
+        let reference = TermReference::parse(reference);
+        let term = normalize_term(reference.term);
+        let context = reference.context.map(normalize_term);
+
+        let candidates: Vec<&TermEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.keys.contains(&term))
+            .filter(|entry| {
+                context
+                    .as_ref()
+                    .is_none_or(|context| &entry.context == context)
+            })
+            .collect();
+
+        let from_context = normalize_term(from_context.unwrap_or_default());
+        candidates
+            .iter()
+            .find(|entry| entry.context == from_context)
+            .or(candidates.first())
+            .map(|entry| entry.anchor.as_str())
+    }
+}
+
+/// コンテキストと用語名から HTML の id を作る (空白や記号は - に置き換える)
+fn anchor_slug(context: &str, term: &str) -> String {
+    let slug: String = format!("{} {}", context, term)
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .flat_map(char::to_lowercase)
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!("term-{}", slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(ubiquitous: &str, context: &str) -> Ubiquitous {
+        Ubiquitous::new()
+            .set_ubiquitous(ubiquitous.to_string())
+            .set_context(context.to_string())
+    }
+
+    #[test]
+    fn test_resolve() {
+        let list = vec![
+            term("Order", "Sales")
+                .add_alias("Purchase".to_string())
+                .set_translated_ubiquitous("ja".to_string(), "注文".to_string()),
+            term("Order", "Shipping"),
+            term("Order Line", "Sales").set_class_name("OrderLine".to_string()),
+        ];
+        let index = TermIndex::new(&list);
+
+        assert_eq!(index.anchor(0), "term-sales-order");
+        assert_eq!(index.resolve("purchase", None), Some("term-sales-order"));
+        assert_eq!(index.resolve("注文", None), Some("term-sales-order"));
+        // 参照元と同じコンテキストを優先する
+        assert_eq!(
+            index.resolve("Order", Some("Shipping")),
+            Some("term-shipping-order")
+        );
+        // コンテキストを明示した参照
+        assert_eq!(
+            index.resolve("Shipping:Order", Some("Sales")),
+            Some("term-shipping-order")
+        );
+        assert_eq!(
+            index.resolve("OrderLine", None),
+            Some("term-sales-order-line")
+        );
+        assert_eq!(index.resolve("Invoice", None), None);
+        assert_eq!(index.resolve("Billing:Order", None), None);
+    }
+
+    #[test]
+    fn test_anchor_unique() {
+        let list = vec![term("Order", ""), term("Order", "")];
+        let index = TermIndex::new(&list);
+
+        assert_eq!(index.anchor(0), "term-order");
+        assert_eq!(index.anchor(1), "term-order-2");
+    }
+}
@@ -0,0 +1,288 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// 説明文中の {@link Term} / [[Term]] を探す正規表現
+static INLINE_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{@link\s+([^}]+)\}|\[\[([^\]]+)\]\]").unwrap());
+
+/// ほかの用語への参照 (例: "Order", コンテキストを指定する場合は "Sales:Order")
+/// Javadoc 形式の "Order#place()" はクラス部分の "Order" を用語として扱う
+#[derive(Debug, PartialEq)]
+pub struct TermReference<'a> {
+    pub context: Option<&'a str>,
+    pub term: &'a str,
+    /// "#" の後ろのメソッド・フィールド (例: "Order#place()" の "place()")
+    pub member: Option<&'a str>,
+}
+
+impl<'a> TermReference<'a> {
+    pub fn parse(reference: &'a str) -> Self {
+        let reference = reference.trim();
+        let (context, rest) = match split_context(reference) {
+            Some((context, term)) if !context.trim().is_empty() && !term.trim().is_empty() => {
+                (Some(context.trim()), term.trim())
+            }
+            _ => (None, reference),
+        };
+        let (term, member) = match rest.split_once('#') {
+            Some((term, member)) => (term.trim(), Some(member.trim())),
+            None => (rest, None),
+        };
+        // Foo.bar() のような呼び出しは括弧の前までを用語とする
+        let term = match term.find('(') {
+            Some(index) if term.ends_with(')') => term[..index].trim(),
+            _ => term,
+        };
+        TermReference {
+            context,
+            term,
+            member,
+        }
+    }
+
+    /// 表示用の文字列 (コンテキストを除き、メンバーは残す)
+    pub fn label(&self) -> String {
+        match self.member {
+            Some(member) => format!("{}#{}", self.term, member),
+            None => self.term.to_string(),
+        }
+    }
+}
+
+/// "Sales:Order" をコンテキストと用語に分ける
+/// Ruby・PHP の "Billing::Invoice"・"Foo::bar" や URL の ":" では分けない
+fn split_context(reference: &str) -> Option<(&str, &str)> {
+    if reference.contains("://") {
+        return None;
+    }
+    let bytes = reference.as_bytes();
+    let index = (0..bytes.len()).find(|&i| {
+        bytes[i] == b':'
+            && (i == 0 || bytes[i - 1] != b':')
+            && bytes.get(i + 1).is_none_or(|&next| next != b':')
+    })?;
+    Some((&reference[..index], &reference[index + 1..]))
+}
+
+/// 用語名ではなくコードを指していると思われる参照かどうか
+/// (Javadoc の @see Foo#bar()、{@link java.util.List}、Ruby の Billing::Invoice、PHP の App\Order、URL など)
+pub fn is_code_reference(reference: &str) -> bool {
+    let reference = reference.trim();
+    if reference.starts_with(['<', '"']) {
+        return true;
+    }
+    if ["#", "(", "::", "\\", "://"]
+        .iter()
+        .any(|marker| reference.contains(marker))
+    {
+        return true;
+    }
+    // パッケージ名を含むクラス名 (java.util.List)
+    reference.contains('.')
+        && !reference.starts_with('.')
+        && !reference.ends_with('.')
+        && reference
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '$'))
+}
+
+/// 説明文を、通常の文字列と参照に分けたもの
+#[derive(Debug, PartialEq)]
+pub enum DescriptionSegment<'a> {
+    Text(&'a str),
+    /// {@link ...} / [[...]] の中身
+    Reference {
+        /// 参照先 (例: "Sales:Order")
+        target: &'a str,
+        /// {@link Order the order} のように参照先の後ろに書かれた表示名
+        label: Option<&'a str>,
+    },
+}
+
+/// 説明文を {@link Term} / [[Term]] の前後で分割する
+pub fn split_inline_references(text: &str) -> Vec<DescriptionSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut last = 0;
+    for captures in INLINE_REFERENCE.captures_iter(text) {
+        let whole = captures.get(0).unwrap();
+        if whole.start() > last {
+            segments.push(DescriptionSegment::Text(&text[last..whole.start()]));
+        }
+        // Javadoc・KDoc の {@link} は参照先の後ろに表示名を書ける ([[...]] は全体が用語名)
+        let (target, label) = match (captures.get(1), captures.get(2)) {
+            (Some(link), _) => split_link_label(link.as_str().trim()),
+            (None, Some(reference)) => (reference.as_str().trim(), None),
+            (None, None) => unreachable!("one of the alternatives matched"),
+        };
+        segments.push(DescriptionSegment::Reference { target, label });
+        last = whole.end();
+    }
+    if last < text.len() {
+        segments.push(DescriptionSegment::Text(&text[last..]));
+    }
+    segments
+}
+
+/// {@link ...} の中身を参照先と表示名に分ける
+/// 参照先は括弧の外にある最初の空白まで (例: "Order#place(Customer, int) 注文する")
+fn split_link_label(link: &str) -> (&str, Option<&str>) {
+    let mut depth = 0usize;
+    for (index, c) in link.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                let label = link[index..].trim();
+                return (&link[..index], (!label.is_empty()).then_some(label));
+            }
+            _ => {}
+        }
+    }
+    (link, None)
+}
+
+/// 説明文中の参照先をすべて返す
+pub fn inline_references(text: &str) -> Vec<&str> {
+    split_inline_references(text)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            DescriptionSegment::Reference { target, .. } => Some(target),
+            DescriptionSegment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// 参照を用語名 (表示名があれば表示名) だけの文字列に置き換えた説明文 (Markdown など、リンクを張らない出力用)
+pub fn plain_description(text: &str) -> String {
+    split_inline_references(text)
+        .into_iter()
+        .map(|segment| match segment {
+            DescriptionSegment::Reference { target, label } => label
+                .map(str::to_string)
+                .unwrap_or_else(|| TermReference::parse(target).label()),
+            DescriptionSegment::Text(text) => text.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            TermReference::parse("Sales:Order Line"),
+            TermReference {
+                context: Some("Sales"),
+                term: "Order Line",
+                member: None,
+            }
+        );
+        assert_eq!(
+            TermReference::parse(" Order "),
+            TermReference {
+                context: None,
+                term: "Order",
+                member: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_code_reference() {
+        // :: は名前空間の区切りで、コンテキストの指定ではない
+        assert_eq!(
+            TermReference::parse("Billing::Invoice"),
+            TermReference {
+                context: None,
+                term: "Billing::Invoice",
+                member: None,
+            }
+        );
+        assert_eq!(
+            TermReference::parse("Sales:Foo::bar"),
+            TermReference {
+                context: Some("Sales"),
+                term: "Foo::bar",
+                member: None,
+            }
+        );
+        // メソッド・フィールドはクラス部分を用語とする
+        let reference = TermReference::parse("Sales:Order#place(Customer)");
+        assert_eq!(
+            reference,
+            TermReference {
+                context: Some("Sales"),
+                term: "Order",
+                member: Some("place(Customer)"),
+            }
+        );
+        assert_eq!(reference.label(), "Order#place(Customer)");
+        assert_eq!(TermReference::parse("Order.total()").term, "Order.total");
+        assert_eq!(TermReference::parse("https://example.com").context, None);
+    }
+
+    #[test]
+    fn test_is_code_reference() {
+        assert!(is_code_reference("Foo#bar()"));
+        assert!(is_code_reference("#place"));
+        assert!(is_code_reference("java.util.List"));
+        assert!(is_code_reference("Billing::Invoice"));
+        assert!(is_code_reference(r"App\Domain\Order"));
+        assert!(is_code_reference(
+            "<a href=\"https://example.com\">spec</a>"
+        ));
+        assert!(!is_code_reference("Order Line"));
+        assert!(!is_code_reference("Sales:Order"));
+        assert!(!is_code_reference("注文"));
+    }
+
+    #[test]
+    fn test_split_inline_references() {
+        let segments = split_inline_references("An {@link Order} has [[Sales:Order Line]]s.");
+
+        assert_eq!(
+            segments,
+            vec![
+                DescriptionSegment::Text("An "),
+                DescriptionSegment::Reference {
+                    target: "Order",
+                    label: None
+                },
+                DescriptionSegment::Text(" has "),
+                DescriptionSegment::Reference {
+                    target: "Sales:Order Line",
+                    label: None
+                },
+                DescriptionSegment::Text("s."),
+            ]
+        );
+        assert_eq!(
+            inline_references("An {@link Order} has [[Sales:Order Line]]s."),
+            vec!["Order", "Sales:Order Line"]
+        );
+        assert_eq!(
+            plain_description("An {@link Order} has [[Sales:Order Line]]s."),
+            "An Order has Order Lines."
+        );
+    }
+
+    #[test]
+    fn test_split_inline_references_with_label() {
+        let text = "See {@link Order the order} and {@link Order#place(Customer, int) placing}.";
+
+        assert_eq!(
+            split_inline_references(text)[1],
+            DescriptionSegment::Reference {
+                target: "Order",
+                label: Some("the order"),
+            }
+        );
+        assert_eq!(
+            inline_references(text),
+            vec!["Order", "Order#place(Customer, int)"]
+        );
+        assert_eq!(plain_description(text), "See the order and placing.");
+    }
+}
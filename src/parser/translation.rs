@@ -0,0 +1,6 @@
+/// 用語名・説明の翻訳 (@ubiquitous:ja / @description:ja)
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Translation {
+    pub ubiquitous: Option<String>,
+    pub description: Option<String>,
+}
@@ -0,0 +1,112 @@
+use tree_sitter::Node;
+
+/// node の子孫のうち is_type_reference に当てはまるノード (型名) の文字列を、重複を除いて出現順に返す
+/// 当てはまったノードの内側 (修飾された型名の各部分など) はそれ以上探さない
+/// ただし型引数 (List<OrderLine> の <OrderLine>) は型名から除き、その中の型名を別に探す
+/// is_declaration に当てはまるネストしたクラスなどの宣言の中は、そのクラス自身の参照として扱うため探さない
+pub fn collect_type_references(
+    node: Node,
+    source_code: &[u8],
+    is_type_reference: &dyn Fn(Node) -> bool,
+    is_declaration: &dyn Fn(Node) -> bool,
+) -> Vec<String> {
+    let mut results = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_in(
+            child,
+            source_code,
+            is_type_reference,
+            is_declaration,
+            &mut results,
+        );
+    }
+    results
+}
+
+fn collect_in(
+    node: Node,
+    source_code: &[u8],
+    is_type_reference: &dyn Fn(Node) -> bool,
+    is_declaration: &dyn Fn(Node) -> bool,
+    results: &mut Vec<String>,
+) {
+    if is_declaration(node) {
+        return;
+    }
+    if is_type_reference(node) {
+        let mut type_arguments = Vec::new();
+        find_type_arguments(node, &mut type_arguments);
+
+        // 型引数を除いた部分を型名とする
+        let mut type_name = String::new();
+        let mut start = node.start_byte();
+        for type_argument in type_arguments.iter() {
+            type_name.push_str(&String::from_utf8_lossy(
+                &source_code[start..type_argument.start_byte()],
+            ));
+            start = type_argument.end_byte();
+        }
+        type_name.push_str(&String::from_utf8_lossy(
+            &source_code[start..node.end_byte()],
+        ));
+        let type_name: String = type_name.split_whitespace().collect();
+        if !type_name.is_empty() && !results.contains(&type_name) {
+            results.push(type_name);
+        }
+
+        for type_argument in type_arguments {
+            collect_in(
+                type_argument,
+                source_code,
+                is_type_reference,
+                is_declaration,
+                results,
+            );
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_in(
+            child,
+            source_code,
+            is_type_reference,
+            is_declaration,
+            results,
+        );
+    }
+}
+
+/// 型名のノードの中にある型引数 (type_arguments) のノードを出現順に集める
+fn find_type_arguments<'a>(node: Node<'a>, results: &mut Vec<Node<'a>>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "type_arguments" {
+            results.push(child);
+        } else {
+            find_type_arguments(child, results);
+        }
+    }
+}
+
+/// 型名から名前空間・パッケージを除いた単純名 (例: App\Billing\Invoice → Invoice)
+pub fn simple_type_name(type_name: &str) -> &str {
+    type_name
+        .rsplit(['\\', '.', ':'])
+        .next()
+        .unwrap_or(type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_type_name() {
+        assert_eq!(simple_type_name("\\App\\Billing\\Invoice"), "Invoice");
+        assert_eq!(simple_type_name("billing.Customer"), "Customer");
+        assert_eq!(simple_type_name("Billing::Invoice"), "Invoice");
+        assert_eq!(simple_type_name("Money"), "Money");
+    }
+}
@@ -1,22 +1,71 @@
+use std::collections::BTreeMap;
+
+use crate::parser::{class_doc::DeclarationKind, stereotype::Stereotype, translation::Translation};
+
 #[derive(Debug, PartialEq)]
 pub struct Ubiquitous {
     pub ubiquitous: String,
     pub context: Option<String>,
     pub class_name: Option<String>,
+    /// 名前空間・外側のクラスを含めた完全修飾名
+    pub qualified_name: Option<String>,
+    /// クラス・インターフェース・enum などの種類
+    pub kind: Option<DeclarationKind>,
+    /// メソッド・列挙子などを宣言しているクラスの完全修飾名
+    pub parent: Option<String>,
     pub description: Option<String>,
+    /// 別名・同義語 (@alias / @synonym)
+    pub aliases: Vec<String>,
+    /// 使用をやめた用語であれば置き換え先の用語 (置き換え先がなければ空文字)
+    pub deprecated: Option<String>,
+    /// 関連する用語への参照 (@see / @related。"Context:Term" の形でコンテキストも指定できる)
+    pub related: Vec<String>,
+    /// 集約・エンティティ・値オブジェクトなどの種類
+    pub stereotype: Option<Stereotype>,
+    /// 所属する集約ルートの用語
+    pub aggregate: Option<String>,
+    /// 不変条件・業務ルール (@invariant / @rule)
+    pub rules: Vec<String>,
+    /// 使用例 (@termExample)
+    pub examples: Vec<String>,
+    /// ロケール (ja など) ごとの用語名・説明の翻訳
+    pub translations: BTreeMap<String, Translation>,
+    /// 用語を担当するチーム (@owner、なければ CODEOWNERS の所有者)
+    pub teams: Vec<String>,
+    /// 設定で追加したタグ (tags.extra) の名前と値
+    pub extra_fields: BTreeMap<String, String>,
     pub file_path: Option<String>,
     pub line_number: Option<usize>,
+    /// クラスが属するパッケージ・名前空間・モジュール
+    pub namespace: Option<String>,
+    /// クラスの中で使われている型名 (コンテキストマップの作成に使う)
+    pub type_references: Vec<String>,
 }
 
 impl Ubiquitous {
     pub fn new() -> Self {
         Ubiquitous {
             class_name: None,
+            qualified_name: None,
+            kind: None,
+            parent: None,
             ubiquitous: String::new(),
             context: None,
             description: None,
+            aliases: Vec::new(),
+            deprecated: None,
+            related: Vec::new(),
+            stereotype: None,
+            aggregate: None,
+            rules: Vec::new(),
+            examples: Vec::new(),
+            translations: BTreeMap::new(),
+            teams: Vec::new(),
+            extra_fields: BTreeMap::new(),
             file_path: None,
             line_number: None,
+            namespace: None,
+            type_references: Vec::new(),
         }
     }
 
@@ -25,6 +74,21 @@ impl Ubiquitous {
         self
     }
 
+    pub fn set_qualified_name(mut self, qualified_name: String) -> Self {
+        self.qualified_name = Some(qualified_name);
+        self
+    }
+
+    pub fn set_kind(mut self, kind: DeclarationKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn set_parent(mut self, parent: String) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
     pub fn set_ubiquitous(mut self, ubiquitous: String) -> Self {
         self.ubiquitous = ubiquitous;
         self
@@ -40,6 +104,72 @@ impl Ubiquitous {
         self
     }
 
+    /// 別名を追加する (空のものと重複は無視する)
+    pub fn add_alias(mut self, alias: String) -> Self {
+        if !alias.is_empty() && !self.aliases.contains(&alias) {
+            self.aliases.push(alias);
+        }
+        self
+    }
+
+    pub fn set_deprecated(mut self, replacement: String) -> Self {
+        self.deprecated = Some(replacement);
+        self
+    }
+
+    pub fn add_related(mut self, reference: String) -> Self {
+        if !reference.is_empty() && !self.related.contains(&reference) {
+            self.related.push(reference);
+        }
+        self
+    }
+
+    pub fn set_stereotype(mut self, stereotype: Stereotype) -> Self {
+        self.stereotype = Some(stereotype);
+        self
+    }
+
+    pub fn set_aggregate(mut self, aggregate: String) -> Self {
+        self.aggregate = Some(aggregate);
+        self
+    }
+
+    pub fn add_rule(mut self, rule: String) -> Self {
+        if !rule.is_empty() {
+            self.rules.push(rule);
+        }
+        self
+    }
+
+    pub fn add_example(mut self, example: String) -> Self {
+        if !example.is_empty() {
+            self.examples.push(example);
+        }
+        self
+    }
+
+    pub fn set_translated_ubiquitous(mut self, locale: String, ubiquitous: String) -> Self {
+        self.translations.entry(locale).or_default().ubiquitous = Some(ubiquitous);
+        self
+    }
+
+    pub fn set_translated_description(mut self, locale: String, description: String) -> Self {
+        self.translations.entry(locale).or_default().description = Some(description);
+        self
+    }
+
+    pub fn add_team(mut self, team: String) -> Self {
+        if !team.is_empty() && !self.teams.contains(&team) {
+            self.teams.push(team);
+        }
+        self
+    }
+
+    pub fn set_extra_field(mut self, name: String, value: String) -> Self {
+        self.extra_fields.insert(name, value);
+        self
+    }
+
     pub fn set_file_path(mut self, file_path: String) -> Self {
         self.file_path = Some(file_path);
         self
@@ -50,6 +180,24 @@ impl Ubiquitous {
         self
     }
 
+    pub fn set_namespace(mut self, namespace: String) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    pub fn set_type_references(mut self, type_references: Vec<String>) -> Self {
+        self.type_references = type_references;
+        self
+    }
+
+    /// --owner で指定したチームが担当しているか (先頭の @ と大文字・小文字の違いは無視する)
+    pub fn is_owned_by(&self, team: &str) -> bool {
+        let team = team.trim().trim_start_matches('@');
+        self.teams
+            .iter()
+            .any(|owner| owner.trim_start_matches('@').eq_ignore_ascii_case(team))
+    }
+
     pub fn is_all_none(&self) -> bool {
         self.class_name.is_none()
             && self.ubiquitous.is_empty()